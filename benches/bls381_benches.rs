@@ -343,6 +343,30 @@ fn key_generation(c: &mut Criterion) {
             })
         }),
     );
+
+    let sk = SecretKey::random(&mut rand::thread_rng());
+
+    c.bench(
+        "key generation",
+        Benchmark::new("Derive PublicKey with fixed-base table", move |b| {
+            b.iter(|| {
+                black_box(PublicKey::from_secret_key(&sk));
+            })
+        }),
+    );
+
+    let sk = SecretKey::random(&mut rand::thread_rng());
+
+    c.bench(
+        "key generation",
+        Benchmark::new("Derive PublicKey with generic multiplication", move |b| {
+            b.iter(|| {
+                let mut point = BLSCurve::ecp::ECP::generator().mul(&sk.x);
+                point.affine();
+                black_box(point);
+            })
+        }),
+    );
 }
 
 criterion_group!(
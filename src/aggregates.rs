@@ -2,15 +2,22 @@ extern crate amcl;
 extern crate rand;
 
 use super::amcl_utils::{
-    self, ate2_evaluation, ate_pairing, hash_on_g2, BigNum, GroupG1, GroupG2, FP12,
+    self, ate2_evaluation, ate_pairing, hash_on_g2, u64_to_scalar, BigNum, GroupG1, GroupG2, FP12,
 };
-use super::errors::DecodeError;
+use super::errors::{DecodeError, VerifyError};
 use super::g1::{G1Point, G1Wrapper};
 use super::g2::G2Point;
-use super::keys::PublicKey;
+use super::keys::{PublicKey, SecretKey};
+use super::pop::pop_verify;
 use super::signature::Signature;
 use amcl::bls381::pair;
-use rand::Rng;
+#[cfg(not(feature = "std"))]
+use core::{fmt, ops::AddAssign};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::{prelude::*, ThreadPool};
+#[cfg(feature = "std")]
+use std::{collections::HashSet, fmt, io, ops::AddAssign};
 use BLSCurve::pair::{ate, ate2, fexp};
 
 // Messages should always be 32 bytes
@@ -31,7 +38,6 @@ pub struct AtePair {
 ///
 /// This may be used to verify some AggregateSignature.
 #[derive(Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
 pub struct AggregatePublicKey {
     pub point: G1Point,
 }
@@ -58,12 +64,105 @@ impl AggregatePublicKey {
         agg_key
     }
 
+    /// Instantiate a new aggregate public key from the sum of some SecretKeys, e.g. MPC shares.
+    ///
+    /// The scalars are summed modulo the curve order and only one PublicKey is derived, see
+    /// `PublicKey::from_secret_keys_sum`. This equals aggregating the PublicKey of each SecretKey.
+    pub fn from_secret_keys(sks: &[&SecretKey]) -> Self {
+        let mut agg_key = AggregatePublicKey {
+            point: PublicKey::from_secret_keys_sum(sks).point,
+        };
+        agg_key.point.affine();
+        agg_key
+    }
+
+    /// Instantiate a new aggregate public key from a vector of PublicKeys, adding in parallel.
+    ///
+    /// Sub-ranges of the PublicKeys are summed in parallel and the partial sums are then added
+    /// together. Point addition is associative and commutative, so the result is equal to
+    /// `from_public_keys`.
+    #[cfg(feature = "rayon")]
+    pub fn from_public_keys_par(keys: &[&PublicKey]) -> Self {
+        let point = keys
+            .par_iter()
+            .fold(GroupG1::new, |mut sum, key| {
+                sum.add(key.point.as_raw());
+                sum
+            })
+            .reduce(GroupG1::new, |mut sum_a, sum_b| {
+                sum_a.add(&sum_b);
+                sum_a
+            });
+        let mut agg_key = AggregatePublicKey {
+            point: G1Point::from_raw(point),
+        };
+        agg_key.point.affine();
+        agg_key
+    }
+
+    /// Instantiate a new aggregate public key from a vector of PublicKeys, validating each first.
+    ///
+    /// Every PublicKey must pass `PublicKey::key_validate`, otherwise the index of the first
+    /// invalid PublicKey is returned with the reason and nothing is aggregated.
+    pub fn try_from_public_keys(keys: &[&PublicKey]) -> Result<Self, (usize, DecodeError)> {
+        for (i, key) in keys.iter().enumerate() {
+            key.key_validate().map_err(|e| (i, e))?;
+        }
+        Ok(Self::from_public_keys(keys))
+    }
+
+    /// Instantiate a new aggregate public key from weighted PublicKeys.
+    ///
+    /// Computes `weight_1 * PK_1 + ... + weight_n * PK_n`, where each weight is reduced modulo
+    /// the curve order. Keys with a weight of zero are skipped.
+    pub fn from_weighted(keys: &[(&PublicKey, u64)]) -> Self {
+        let mut agg_key = AggregatePublicKey::new();
+        for (key, weight) in keys {
+            if *weight == 0 {
+                continue;
+            }
+            let point = key.point.as_raw().mul(&u64_to_scalar(*weight));
+            agg_key.point.add(&G1Point::from_raw(point));
+        }
+        agg_key.point.affine();
+        agg_key
+    }
+
+    /// Instantiate a new aggregate public key from the PublicKeys selected by a bitfield.
+    ///
+    /// Bit `i % 8` of byte `i / 8` selects `all_pubkeys[i]` (little-endian within each byte).
+    /// Returns `None` if `bits` is not exactly `ceil(all_pubkeys.len() / 8)` bytes or if any of
+    /// the padding bits past the last PublicKey are set.
+    pub fn from_bitfield(all_pubkeys: &[PublicKey], bits: &[u8]) -> Option<Self> {
+        if bits.len() != (all_pubkeys.len() + 7) / 8 {
+            return None;
+        }
+        let padding = bits.len() * 8 - all_pubkeys.len();
+        if padding > 0 && bits[bits.len() - 1] >> (8 - padding) != 0 {
+            return None;
+        }
+
+        let mut agg_key = AggregatePublicKey::new();
+        for (i, key) in all_pubkeys.iter().enumerate() {
+            if bits[i / 8] >> (i % 8) & 1 == 1 {
+                agg_key.point.add(&key.point);
+            }
+        }
+        agg_key.point.affine();
+        Some(agg_key)
+    }
+
     /// Add a PublicKey to the AggregatePublicKey.
     pub fn add(&mut self, public_key: &PublicKey) {
         self.point.add(&public_key.point);
         //self.point.affine();
     }
 
+    /// Subtract a PublicKey from the AggregatePublicKey, undoing a previous `add`.
+    pub fn subtract(&mut self, public_key: &PublicKey) {
+        self.point.add(&public_key.neg().point);
+    }
+
     /// Add a AggregatePublicKey to the AggregatePublicKey.
     pub fn add_aggregate(&mut self, aggregate_public_key: &AggregatePublicKey) {
         self.point.add(&aggregate_public_key.point);
@@ -76,11 +175,39 @@ impl AggregatePublicKey {
         Ok(Self { point })
     }
 
+    /// Instantiate an AggregatePublicKey from compressed bytes, skipping the subgroup check.
+    ///
+    /// Only use this for bytes which are already known to be valid (see
+    /// `G1Point::from_bytes_unchecked`).
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<AggregatePublicKey, DecodeError> {
+        let point = G1Point::from_bytes_unchecked(bytes)?;
+        Ok(Self { point })
+    }
+
     /// Export the AggregatePublicKey to compressed bytes.
+    ///
+    /// The bytes are canonical: they depend only on the PublicKeys added, not the order they
+    /// were added in or whether the point has been converted to affine.
     pub fn as_bytes(&self) -> Vec<u8> {
         let mut clone = self.point.clone();
         clone.as_bytes()
     }
+
+    /// Write the compressed bytes to some `io::Write`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_bytes())
+    }
+
+    /// Read exactly 48 compressed bytes from some `io::Read` and decode them.
+    ///
+    /// Decoding failures are returned as `io::ErrorKind::InvalidData`.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<AggregatePublicKey> {
+        let mut bytes = [0; amcl_utils::MODBYTES];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
 }
 
 impl Default for AggregatePublicKey {
@@ -89,11 +216,35 @@ impl Default for AggregatePublicKey {
     }
 }
 
+/// Adds each PublicKey in turn, as with `add`.
+///
+/// The point is not converted to affine, call `point.affine()` afterwards if required.
+impl Extend<PublicKey> for AggregatePublicKey {
+    fn extend<T: IntoIterator<Item = PublicKey>>(&mut self, iter: T) {
+        for public_key in iter {
+            self.add(&public_key);
+        }
+    }
+}
+
+impl<'a> Extend<&'a PublicKey> for AggregatePublicKey {
+    fn extend<T: IntoIterator<Item = &'a PublicKey>>(&mut self, iter: T) {
+        for public_key in iter {
+            self.add(public_key);
+        }
+    }
+}
+
+impl fmt::Debug for AggregatePublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        amcl_utils::fmt_hex(f, &self.as_bytes())
+    }
+}
+
 /// Allows for the adding/combining of multiple BLS Signatures.
 ///
 /// This may be verified against some AggregatePublicKey.
 #[derive(Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
 pub struct AggregateSignature {
     pub point: G2Point,
 }
@@ -108,12 +259,86 @@ impl AggregateSignature {
         }
     }
 
+    /// Instantiate a new AggregateSignature from a vector of Signatures, adding in parallel.
+    ///
+    /// Sub-ranges of the Signatures are summed in parallel and the partial sums are then added
+    /// together. Point addition is associative and commutative, so the result is equal to adding
+    /// each Signature in turn.
+    #[cfg(feature = "rayon")]
+    pub fn from_signatures_par(signatures: &[&Signature]) -> Self {
+        let point = signatures
+            .par_iter()
+            .fold(GroupG2::new, |mut sum, signature| {
+                sum.add(signature.point.as_raw());
+                sum
+            })
+            .reduce(GroupG2::new, |mut sum_a, sum_b| {
+                sum_a.add(&sum_b);
+                sum_a
+            });
+        let mut agg_sig = AggregateSignature {
+            point: G2Point::from_raw(point),
+        };
+        agg_sig.point.affine();
+        agg_sig
+    }
+
+    /// Instantiate a new AggregateSignature from weighted Signatures.
+    ///
+    /// Computes `weight_1 * S_1 + ... + weight_n * S_n`, where each weight is reduced modulo the
+    /// curve order. Signatures with a weight of zero are skipped.
+    ///
+    /// Verifies against an `AggregatePublicKey::from_weighted` using the same weights.
+    pub fn from_weighted(signatures: &[(&Signature, u64)]) -> Self {
+        let mut agg_sig = AggregateSignature::new();
+        for (signature, weight) in signatures {
+            if *weight == 0 {
+                continue;
+            }
+            let point = signature.point.as_raw().mul(&u64_to_scalar(*weight));
+            agg_sig.point.add(&G2Point::from_raw(point));
+        }
+        agg_sig.point.affine();
+        agg_sig
+    }
+
     /// Add a Signature to the AggregateSignature.
     pub fn add(&mut self, signature: &Signature) {
         self.point.add(&signature.point);
         //self.point.affine();
     }
 
+    /// Add a Signature to the AggregateSignature, rejecting the identity.
+    ///
+    /// Adding the identity (point at infinity) leaves the aggregate unchanged, so a participant
+    /// who contributes it is a free rider: paired with the identity PublicKey (see
+    /// `PublicKey::key_validate`) it passes verification without anything being signed, and in
+    /// schemes which count contributions it is credited for a signature it never made. Returns
+    /// `PointAtInfinityDisallowed` and leaves the aggregate unchanged if `signature` is the
+    /// identity.
+    pub fn try_add(&mut self, signature: &Signature) -> Result<(), DecodeError> {
+        if signature.point.is_infinity() {
+            return Err(DecodeError::PointAtInfinityDisallowed);
+        }
+        self.add(signature);
+        Ok(())
+    }
+
+    /// Add a Signature to the AggregateSignature unless it has already been contributed.
+    ///
+    /// `seen` holds the compressed bytes of every Signature added so far; pass the same set for
+    /// every call on an aggregate. A Signature added twice is double counted and the aggregate
+    /// then fails verification, so returns false and leaves the aggregate unchanged if
+    /// `signature` is already in `seen`.
+    #[cfg(feature = "std")]
+    pub fn try_add_unique(&mut self, signature: &Signature, seen: &mut HashSet<Vec<u8>>) -> bool {
+        if !seen.insert(signature.as_bytes()) {
+            return false;
+        }
+        self.add(signature);
+        true
+    }
+
     /// Add a AggregateSignature to the AggregateSignature.
     pub fn add_aggregate(&mut self, aggregate_signature: &AggregateSignature) {
         self.point.add(&aggregate_signature.point);
@@ -123,7 +348,13 @@ impl AggregateSignature {
     /// Verify this AggregateSignature against an AggregatePublicKey.
     ///
     /// Input an AggregateSignature, a AggregatePublicKey and a Message
+    ///
+    /// Returns false if the AggregatePublicKey is the identity, e.g. the aggregate of no
+    /// PublicKeys: a Message is never verified against an empty set of PublicKeys.
     pub fn verify(&self, msg: &[u8], domain: u64, avk: &AggregatePublicKey) -> bool {
+        if avk.point.is_infinity() {
+            return false;
+        }
         let mut sig_point = self.point.clone();
         let mut key_point = avk.point.clone();
         sig_point.affine();
@@ -132,8 +363,7 @@ impl AggregateSignature {
         msg_hash_point.affine();
 
         // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
-        let mut generator_g1_negative = amcl_utils::GroupG1::generator();
-        generator_g1_negative.neg();
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
         ate2_evaluation(
             &sig_point.as_raw(),
             &generator_g1_negative,
@@ -142,44 +372,318 @@ impl AggregateSignature {
         )
     }
 
+    /// Verify this AggregateSignature against an AggregatePublicKey using a given negated G1
+    /// generator.
+    ///
+    /// As `verify` but checks e(S, neg_g1) * e(H, PK) == 1, so a cached negated generator (e.g.
+    /// `PublicKey::negative_generator()`) or a non-standard generator may be used. Returns false
+    /// if `neg_g1` or the AggregatePublicKey is the point at infinity.
+    pub fn verify_with_neg_generator(
+        &self,
+        msg: &[u8],
+        domain: u64,
+        avk: &AggregatePublicKey,
+        neg_g1: &G1Point,
+    ) -> bool {
+        if neg_g1.is_infinity() || avk.point.is_infinity() {
+            return false;
+        }
+
+        let mut sig_point = self.point.clone();
+        let mut key_point = avk.point.clone();
+        let mut neg_g1 = neg_g1.clone();
+        sig_point.affine();
+        key_point.affine();
+        neg_g1.affine();
+        let mut msg_hash_point = hash_on_g2(msg, domain);
+        msg_hash_point.affine();
+
+        ate2_evaluation(
+            &sig_point.as_raw(),
+            &neg_g1.as_raw(),
+            &msg_hash_point,
+            &key_point.as_raw(),
+        )
+    }
+
+    /// Verify this AggregateSignature against an AggregatePublicKey and a 32 byte eth2 signing
+    /// root.
+    pub fn verify_signing_root(
+        &self,
+        root: &[u8; 32],
+        domain: u64,
+        avk: &AggregatePublicKey,
+    ) -> bool {
+        self.verify(root, domain, avk)
+    }
+
+    /// Verify this AggregateSignature against one Message signed by every PublicKey, where each
+    /// PublicKey comes with its proof of possession.
+    ///
+    /// Every proof is checked with `pop_verify` before the PublicKeys are aggregated, which
+    /// protects against rogue key attacks. Returns false on the first invalid proof or if there
+    /// are no PublicKeys.
+    pub fn fast_aggregate_verify_pop(
+        &self,
+        msg: &[u8],
+        domain: u64,
+        keys_with_pops: &[(&PublicKey, &Signature)],
+    ) -> bool {
+        if keys_with_pops.is_empty() {
+            return false;
+        }
+
+        let mut avk = AggregatePublicKey::new();
+        for (pk, pop) in keys_with_pops {
+            if !pop_verify(pk, pop) {
+                return false;
+            }
+            avk.add(pk);
+        }
+        self.verify(msg, domain, &avk)
+    }
+
+    /// Verify this AggregateSignature against multiple Messages all signed by one PublicKey.
+    ///
+    /// As the PublicKey is shared the check `e(S, -G1) * e(H(m_1), PK) * ... * e(H(m_n), PK) == 1`
+    /// is reduced to `e(S, -G1) * e(H(m_1) + ... + H(m_n), PK) == 1`, which needs only two
+    /// pairings. Returns false if there are no Messages or the PublicKey is the identity.
+    pub fn verify_single_key_multi_message(
+        &self,
+        msgs: &[&[u8]],
+        domain: u64,
+        pk: &PublicKey,
+    ) -> bool {
+        if msgs.is_empty() || pk.point.is_infinity() {
+            return false;
+        }
+
+        let mut sig_point = self.point.clone();
+        sig_point.affine();
+
+        let mut msg_hash_point = GroupG2::new();
+        for msg in msgs {
+            msg_hash_point.add(&hash_on_g2(msg, domain));
+        }
+        msg_hash_point.affine();
+
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
+        ate2_evaluation(
+            &sig_point.as_raw(),
+            &generator_g1_negative,
+            &msg_hash_point,
+            &pk.point.as_raw(),
+        )
+    }
+
     /// Verify this AggregateSignature against multiple AggregatePublickeys with multiple Messages.
     ///
     /// All PublicKeys related to a Message should be aggregated into one AggregatePublicKey.
     /// Each AggregatePublicKey has a 1:1 ratio with a 32 byte Message.
+    ///
+    /// This is AggregateVerify, which is only secure for distinct Messages, so returns false if
+    /// any two Messages are equal. Use `verify` when every PublicKey signed the same Message.
     pub fn verify_multiple(
         &self,
         msg: &[Vec<u8>],
         domain: u64,
         apks: &[&AggregatePublicKey],
     ) -> bool {
-        let mut sig_point = self.point.clone();
-        sig_point.affine();
+        self.try_verify_multiple(msg, domain, apks).is_ok()
+    }
+
+    /// As `verify_multiple`, but returns the reason verification failed.
+    ///
+    /// The inputs are validated in order (lengths, Message sizes, then duplicate Messages) before
+    /// any hashing, so a malformed input is reported without computing any pairings.
+    ///
+    /// With no Messages the only valid AggregateSignature is the identity, the aggregate of no
+    /// Signatures, so that is accepted and any other is `VerifyError::Empty`.
+    pub fn try_verify_multiple(
+        &self,
+        msg: &[Vec<u8>],
+        domain: u64,
+        apks: &[&AggregatePublicKey],
+    ) -> Result<(), VerifyError> {
+        if self.is_empty_aggregate_of(msg, apks.len()) {
+            return Ok(());
+        }
+        check_multiple_inputs(msg, apks.len())?;
+
+        if self.verify_multiple_unchecked(msg.iter().map(|msg| msg.as_slice()), domain, apks) {
+            Ok(())
+        } else {
+            Err(VerifyError::InvalidSignature)
+        }
+    }
+
+    /// As `verify_multiple`, but each Message may be signed by a single PublicKey or by an
+    /// AggregatePublicKey.
+    ///
+    /// Both are `G1Wrapper`s, so single PublicKeys need not be wrapped in one element
+    /// AggregatePublicKeys first. As in `verify_multiple` the identity is valid for no Messages.
+    pub fn verify_multiple_mixed(
+        &self,
+        msg: &[Vec<u8>],
+        domain: u64,
+        keys: &[&dyn G1Wrapper],
+    ) -> bool {
+        if self.is_empty_aggregate_of(msg, keys.len()) {
+            return true;
+        }
+        check_multiple_inputs(msg, keys.len()).is_ok()
+            && self.verify_multiple_unchecked(msg.iter().map(|msg| msg.as_slice()), domain, keys)
+    }
+
+    /// As `verify_multiple`, but first aggregates the individual Signatures over the Messages.
+    ///
+    /// The Signatures are summed with a single `affine()` at the end, there may be any number of
+    /// them for each Message. The Messages and AggregatePublicKeys are checked as in
+    /// `verify_multiple`.
+    pub fn verify_multiple_from_signatures(
+        sigs: &[&Signature],
+        msgs: &[&[u8]],
+        domain: u64,
+        apks: &[&AggregatePublicKey],
+    ) -> bool {
+        let mut agg_sig = AggregateSignature::new();
+        for sig in sigs {
+            agg_sig.add(sig);
+        }
+        agg_sig.point.affine();
 
-        // Messages are 32 bytes and need a 1:1 ratio to AggregatePublicKeys
-        if msg.len() != apks.len() || apks.is_empty() {
+        if agg_sig.is_empty_aggregate_of(msgs, apks.len()) {
+            return true;
+        }
+        check_multiple_inputs(msgs, apks.len()).is_ok()
+            && agg_sig.verify_multiple_unchecked(msgs.iter().cloned(), domain, apks)
+    }
+
+    // Whether this is the identity with no Messages and no PublicKeys, the aggregate of no
+    // Signatures, which AggregateVerify accepts.
+    fn is_empty_aggregate_of<M>(&self, msg: &[M], pubkeys: usize) -> bool {
+        msg.is_empty() && pubkeys == 0 && self.point.is_infinity()
+    }
+
+    /// Verify this AggregateSignature against parallel lists of PublicKeys and the Message each
+    /// one signed, where a Message may repeat.
+    ///
+    /// PublicKeys are aggregated per distinct Message and then checked with one multi-pairing,
+    /// `e(S, -G1) * e(H(m_1), apk_1) * ... * e(H(m_k), apk_k) == 1`. Combining keys over a shared
+    /// Message is only secure with proof of possession, as in `verify`. Returns false if the
+    /// number of PublicKeys and Messages differ. With none only the identity is accepted, as in
+    /// `try_verify_multiple`.
+    pub fn aggregate_verify_grouped(
+        &self,
+        pubkeys: &[&PublicKey],
+        msgs: &[&[u8]],
+        domain: u64,
+    ) -> bool {
+        if self.is_empty_aggregate_of(msgs, pubkeys.len()) {
+            return true;
+        }
+        if pubkeys.len() != msgs.len() || msgs.is_empty() {
+            return false;
+        }
+
+        // Sort by Message so equal Messages are adjacent
+        let mut pairs: Vec<(&[u8], &PublicKey)> =
+            msgs.iter().cloned().zip(pubkeys.iter().cloned()).collect();
+        pairs.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut grouped_msgs: Vec<&[u8]> = Vec::with_capacity(pairs.len());
+        let mut apks: Vec<AggregatePublicKey> = Vec::with_capacity(pairs.len());
+        for (msg, pubkey) in pairs {
+            if grouped_msgs.last() != Some(&msg) {
+                grouped_msgs.push(msg);
+                apks.push(AggregatePublicKey::new());
+            }
+            if let Some(apk) = apks.last_mut() {
+                apk.add(pubkey);
+            }
+        }
+
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
+        self.verify_multiple_unchecked(grouped_msgs.into_iter(), domain, &apk_refs)
+    }
+
+    /// As `verify_multiple`, but takes (Message, AggregatePublicKey) pairs from any iterator.
+    ///
+    /// Messages can be anything which borrows as bytes, e.g. `&[u8]` fields of a larger
+    /// structure, so they need not be cloned into a `Vec<Vec<u8>>`. Returns false if any Message
+    /// is not 32 bytes or if any two Messages are equal. With no pairs only the identity is
+    /// accepted.
+    pub fn verify_multiple_iter<'a, I, M>(&self, pairs: I, domain: u64) -> bool
+    where
+        I: IntoIterator<Item = (M, &'a AggregatePublicKey)>,
+        M: AsRef<[u8]>,
+    {
+        let (msgs, apks): (Vec<M>, Vec<&AggregatePublicKey>) = pairs.into_iter().unzip();
+        if self.is_empty_aggregate_of(&msgs, apks.len()) {
+            return true;
+        }
+        if apks.is_empty() || msgs.iter().any(|msg| msg.as_ref().len() != MSG_LENGTH) {
+            return false;
+        }
+        if first_duplicate_message(msgs.iter().map(|msg| msg.as_ref())).is_some() {
+            return false;
+        }
+        self.verify_multiple_unchecked(msgs.iter().map(|msg| msg.as_ref()), domain, &apks)
+    }
+
+    /// Verify this AggregateSignature against multiple AggregatePublicKeys, one per 32 byte
+    /// eth2 signing root.
+    ///
+    /// As in `verify_multiple`, except the signing roots are fixed size so no length checks are
+    /// needed. The signing roots must be distinct. With no signing roots only the identity is
+    /// accepted.
+    pub fn verify_multiple_signing_roots(
+        &self,
+        roots: &[[u8; 32]],
+        domain: u64,
+        apks: &[&AggregatePublicKey],
+    ) -> bool {
+        if self.is_empty_aggregate_of(roots, apks.len()) {
+            return true;
+        }
+        if roots.len() != apks.len() || apks.is_empty() {
+            return false;
+        }
+        if first_duplicate_message(roots.iter().map(|root| &root[..])).is_some() {
+            return false;
+        }
+        self.verify_multiple_unchecked(roots.iter().map(|root| &root[..]), domain, apks)
+    }
+
+    // Checks e(S, -G1) * e(H(msg_1), apk_1) * ... * e(H(msg_n), apk_n) == 1, the number of
+    // Messages and AggregatePublicKeys must already have been checked.
+    fn verify_multiple_unchecked<'a, I, K>(&self, msgs: I, domain: u64, apks: &[&K]) -> bool
+    where
+        I: Iterator<Item = &'a [u8]>,
+        K: G1Wrapper + ?Sized,
+    {
+        // An identity key contributes nothing, so its Message would go unchecked
+        if apks.iter().any(|apk| apk.point().is_infinity()) {
             return false;
         }
 
+        let mut sig_point = self.point.clone();
+        sig_point.affine();
+
         // Add pairings for aggregates: e(H(msg1), pk1) * ... * e(H(msgn), pkn)
         let mut r = pair::initmp();
-
-        for (i, aggregate_public_key) in apks.iter().enumerate() {
-            let mut key_point = aggregate_public_key.point.clone();
+        for (msg, aggregate_public_key) in msgs.zip(apks.iter()) {
+            let mut key_point = aggregate_public_key.point().clone();
             key_point.affine();
 
-            // Messages should always be 32 bytes
-            if msg[i].len() != MSG_LENGTH {
-                return false;
-            }
-            let mut hash_point = hash_on_g2(&msg[i], domain);
+            let mut hash_point = hash_on_g2(msg, domain);
             hash_point.affine();
 
             pair::another(&mut r, &hash_point, &key_point.as_raw().clone());
         }
 
         // Multiply by signature pairing: e(S, -G1)
-        let mut negative_g1 = GroupG1::generator();
-        negative_g1.neg();
+        let negative_g1 = amcl_utils::generator_g1_negative();
         pair::another(&mut r, &sig_point.as_raw(), &negative_g1);
 
         // Complete pairing and verify output is 1.
@@ -188,79 +692,784 @@ impl AggregateSignature {
         v.isunity()
     }
 
+    /// Verify this AggregateSignature against multiple AggregatePublickeys with multiple Messages,
+    /// validating all inputs before any hashing.
+    ///
+    /// Returns false immediately if the number of Messages and AggregatePublicKeys differ or if
+    /// any Message is not 32 bytes. Otherwise this is `verify_multiple`, so with no Messages
+    /// only the identity is accepted.
+    pub fn verify_multiple_strict(
+        &self,
+        msg: &[Vec<u8>],
+        domain: u64,
+        apks: &[&AggregatePublicKey],
+    ) -> bool {
+        if msg.len() != apks.len() || msg.iter().any(|msg| msg.len() != MSG_LENGTH) {
+            return false;
+        }
+        self.verify_multiple(msg, domain, apks)
+    }
+
     /// Verify Multiple AggregateSignatures
     ///
     /// Input (AggregateSignature, PublicKey[m], Messages(Vec<u8>)[m])[n]
     /// Checks that each AggregateSignature is valid with a reduced number of pairings.
     /// https://ethresear.ch/t/fast-verification-of-multiple-bls-signatures/5407
+    ///
+    /// Returns false if any PublicKey is the identity, as in `AggregateSignature::verify`.
     pub fn verify_multiple_signatures<R, I>(rng: &mut R, signature_sets: I) -> bool
     where
-        R: Rng + ?Sized,
+        R: RngCore + CryptoRng + ?Sized,
         I: Iterator<Item = (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)>,
+    {
+        Self::verify_multiple_signatures_with(signature_sets, || {
+            let mut rand = [0 as u8; 8]; // bytes
+            rng.fill_bytes(&mut rand);
+            coefficient_from_bytes(rand)
+        })
+    }
+
+    /// Verify Multiple AggregateSignatures where the signers of each Message are aggregated.
+    ///
+    /// Input (AggregateSignature, (Message, AggregatePublicKey)[m], domain)[n]
+    /// As `verify_multiple_signatures`, but each Message in a set comes with the
+    /// AggregatePublicKey of everyone who signed it, so there is one scalar multiplication and
+    /// one pairing per Message rather than per PublicKey. The check is the same.
+    pub fn verify_multiple_signatures_aggregated<R, I>(rng: &mut R, signature_sets: I) -> bool
+    where
+        R: RngCore + CryptoRng + ?Sized,
+        I: Iterator<Item = (G2Point, Vec<(Vec<u8>, AggregatePublicKey)>, u64)>,
+    {
+        Self::verify_multiple_signatures(
+            rng,
+            signature_sets.map(|(g2_point, pairs, domain)| {
+                let (msgs, g1_points): (Vec<Vec<u8>>, Vec<G1Point>) =
+                    pairs.into_iter().map(|(msg, apk)| (msg, apk.point)).unzip();
+                (g2_point, g1_points, msgs, domain)
+            }),
+        )
+    }
+
+    /// Verify Multiple AggregateSignatures with coefficients derived from the inputs.
+    ///
+    /// As `verify_multiple_signatures`, but rather than using an Rng each coefficient is taken
+    /// from SHA256 over a transcript of every AggregateSignature, PublicKey, Message and domain
+    /// (Fiat-Shamir). Identical inputs always give identical coefficients and the same result.
+    pub fn verify_multiple_signatures_deterministic<I>(signature_sets: I) -> bool
+    where
+        I: Iterator<Item = (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)>,
+    {
+        let signature_sets: Vec<(G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)> =
+            signature_sets.collect();
+
+        // Every input is committed to before any coefficient is derived
+        let mut transcript = vec![];
+        for (g2_point, g1_points, msgs, domain) in &signature_sets {
+            transcript.extend_from_slice(&g2_point.clone().as_bytes());
+            transcript.extend_from_slice(&(g1_points.len() as u64).to_be_bytes());
+            for g1_point in g1_points {
+                transcript.extend_from_slice(&g1_point.clone().as_bytes());
+            }
+            transcript.extend_from_slice(&(msgs.len() as u64).to_be_bytes());
+            for msg in msgs {
+                transcript.extend_from_slice(&(msg.len() as u64).to_be_bytes());
+                transcript.extend_from_slice(msg);
+            }
+            transcript.extend_from_slice(&domain.to_be_bytes());
+        }
+        let transcript_hash = amcl_utils::hash(&transcript);
+
+        let mut counter: u64 = 0;
+        Self::verify_multiple_signatures_with(signature_sets.into_iter(), || {
+            let digest = amcl_utils::hash(&[&transcript_hash[..], &counter.to_be_bytes()].concat());
+            counter += 1;
+            let mut rand = [0 as u8; 8];
+            rand.copy_from_slice(&digest[..8]);
+            coefficient_from_bytes(rand)
+        })
+    }
+
+    // Checks e(r_1 * S_1 + ... + r_n * S_n, -G1) * e(H(m_1_1), r_1 * PK_1_1) * ... == 1
+    // where each coefficient r_i is taken from `coefficient`.
+    fn verify_multiple_signatures_with<I, F>(signature_sets: I, mut coefficient: F) -> bool
+    where
+        I: Iterator<Item = (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)>,
+        F: FnMut() -> BigNum,
     {
         let mut final_agg_sig = GroupG2::new(); // Aggregates AggregateSignature
 
-        // Stores current value of pairings
-        let mut r = pair::initmp();
+        // Stores current value of pairings
+        let mut r = pair::initmp();
+        for (g2_point, g1_points, msgs, domain) in signature_sets {
+            // An identity key contributes nothing, so its Message would go unchecked
+            if g1_points.len() != msgs.len() || g1_points.iter().any(|p| p.is_infinity()) {
+                return false;
+            }
+
+            let rand = coefficient();
+
+            msgs.into_iter()
+                .zip(g1_points.into_iter())
+                .for_each(|(msg, g1_point)| {
+                    let mut hash_point = hash_on_g2(&msg, domain);
+                    hash_point.affine();
+
+                    let mut public_key = g1_point.into_raw().mul(&rand);
+                    public_key.affine();
+
+                    // Update current pairings: *= e(msg, ri * PK)
+                    pair::another(&mut r, &hash_point, &public_key);
+                });
+
+            // Multiply Signature by r and add it to final aggregate signature
+            let temp_sig = g2_point.as_raw().mul(&rand); // AggregateSignature[i] * r
+            final_agg_sig.add(&temp_sig);
+        }
+        final_agg_sig.affine();
+
+        // Pairing for LHS - e(S', G1)
+        let negative_g1 = amcl_utils::generator_g1_negative();
+        pair::another(&mut r, &final_agg_sig, &negative_g1);
+
+        // Complete pairing and verify output is 1.
+        let mut v = pair::miller(&r);
+        v = pair::fexp(&v);
+        v.isunity()
+    }
+
+    /// Verify Multiple AggregateSignatures in parallel on the given rayon ThreadPool.
+    ///
+    /// As `verify_multiple_signatures`, but the hashing and Miller loops for each set run on
+    /// `pool` rather than the global rayon pool, so the caller controls how many threads are
+    /// used. The coefficients are drawn from `rng` before any work is sent to the pool.
+    #[cfg(feature = "rayon")]
+    pub fn verify_multiple_signatures_with_pool<R, I>(
+        pool: &ThreadPool,
+        rng: &mut R,
+        signature_sets: I,
+    ) -> bool
+    where
+        R: RngCore + CryptoRng + ?Sized,
+        I: Iterator<Item = (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)>,
+    {
+        let signature_sets: Vec<((G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64), BigNum)> =
+            signature_sets
+                .map(|set| {
+                    let mut rand = [0 as u8; 8]; // bytes
+                    rng.fill_bytes(&mut rand);
+                    (set, coefficient_from_bytes(rand))
+                })
+                .collect();
+        if signature_sets.iter().any(|((_, g1_points, msgs, _), _)| {
+            g1_points.len() != msgs.len() || g1_points.iter().any(|p| p.is_infinity())
+        }) {
+            return false;
+        }
+
+        pool.install(|| {
+            // Each set gives r_i * S_i and the Miller loop of its e(H(m_i_j), r_i * PK_i_j)
+            let (mut final_agg_sig, miller_product) = signature_sets
+                .par_iter()
+                .map(|((g2_point, g1_points, msgs, domain), rand)| {
+                    let mut r = pair::initmp();
+                    for (msg, g1_point) in msgs.iter().zip(g1_points.iter()) {
+                        let mut hash_point = hash_on_g2(msg, *domain);
+                        hash_point.affine();
+
+                        let mut public_key = g1_point.as_raw().mul(rand);
+                        public_key.affine();
+
+                        pair::another(&mut r, &hash_point, &public_key);
+                    }
+                    (g2_point.as_raw().mul(rand), pair::miller(&r))
+                })
+                .reduce(
+                    || (GroupG2::new(), FP12::new_int(1)),
+                    |(mut sig_a, mut miller_a), (sig_b, miller_b)| {
+                        sig_a.add(&sig_b);
+                        miller_a.mul(&miller_b);
+                        (sig_a, miller_a)
+                    },
+                );
+            final_agg_sig.affine();
+
+            // Pairing for LHS - e(S', G1)
+            let mut r = pair::initmp();
+            let negative_g1 = amcl_utils::generator_g1_negative();
+            pair::another(&mut r, &final_agg_sig, &negative_g1);
+
+            // Combine the Miller loops and verify output is 1.
+            let mut v = pair::miller(&r);
+            v.mul(&miller_product);
+            v = pair::fexp(&v);
+            v.isunity()
+        })
+    }
+
+    /// Verify Multiple AggregateSignatures given as SignatureSets.
+    ///
+    /// Accepts anything which iterates over `&SignatureSet`, e.g. `&[SignatureSet]`.
+    /// See `verify_multiple_signatures`.
+    pub fn verify_signature_sets<'a, R, I>(rng: &mut R, signature_sets: I) -> bool
+    where
+        R: RngCore + CryptoRng + ?Sized,
+        I: IntoIterator<Item = &'a SignatureSet>,
+    {
+        Self::verify_multiple_signatures(
+            rng,
+            signature_sets
+                .into_iter()
+                .map(|set| set.clone().into_tuple()),
+        )
+    }
+
+    /// Find the invalid AggregateSignatures in a batch.
+    ///
+    /// Input (AggregateSignature, PublicKey[m], Messages(Vec<u8>)[m])[n]
+    /// The batch is verified with `verify_multiple_signatures`; if it fails it is split in half
+    /// and each half is checked recursively, so `k` invalid sets are found in `O(k log n)` batch
+    /// verifications. Returns the indices of the invalid sets in ascending order.
+    pub fn find_invalid_signatures<R>(
+        rng: &mut R,
+        signature_sets: &[(G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)],
+    ) -> Vec<usize>
+    where
+        R: RngCore + CryptoRng + ?Sized,
+    {
+        let mut invalid = vec![];
+        Self::find_invalid_signatures_from(rng, signature_sets, 0, &mut invalid);
+        invalid
+    }
+
+    // Recursively search `signature_sets` for invalid sets, where `offset` is the index of the
+    // first set in the original batch.
+    fn find_invalid_signatures_from<R>(
+        rng: &mut R,
+        signature_sets: &[(G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64)],
+        offset: usize,
+        invalid: &mut Vec<usize>,
+    ) where
+        R: RngCore + CryptoRng + ?Sized,
+    {
+        if signature_sets.is_empty()
+            || Self::verify_multiple_signatures(rng, signature_sets.iter().cloned())
+        {
+            return;
+        }
+
+        if signature_sets.len() == 1 {
+            invalid.push(offset);
+            return;
+        }
+
+        let mid = signature_sets.len() / 2;
+        Self::find_invalid_signatures_from(rng, &signature_sets[..mid], offset, invalid);
+        Self::find_invalid_signatures_from(rng, &signature_sets[mid..], offset + mid, invalid);
+    }
+
+    /// Instatiate an AggregateSignature from some bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AggregateSignature, DecodeError> {
+        let point = G2Point::from_bytes(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Instatiate an AggregateSignature from some bytes, skipping the subgroup check.
+    ///
+    /// Only use this for bytes which are already known to be valid (see
+    /// `G2Point::from_bytes_unchecked`).
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<AggregateSignature, DecodeError> {
+        let point = G2Point::from_bytes_unchecked(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Export (serialize) the AggregateSignature to bytes.
+    ///
+    /// The bytes are canonical: they depend only on the Signatures added, not the order they
+    /// were added in or whether the point has been converted to affine.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut clone = self.point.clone();
+        clone.as_bytes()
+    }
+
+    /// Write the compressed bytes to some `io::Write`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_bytes())
+    }
+
+    /// Read exactly 96 compressed bytes from some `io::Read` and decode them.
+    ///
+    /// Decoding failures are returned as `io::ErrorKind::InvalidData`.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<AggregateSignature> {
+        let mut bytes = [0; amcl_utils::G2_BYTE_SIZE / 2];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+}
+
+impl Default for AggregateSignature {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adds each Signature in turn, as with `add`.
+///
+/// The point is not converted to affine, call `point.affine()` afterwards if required.
+impl Extend<Signature> for AggregateSignature {
+    fn extend<T: IntoIterator<Item = Signature>>(&mut self, iter: T) {
+        for signature in iter {
+            self.add(&signature);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Signature> for AggregateSignature {
+    fn extend<T: IntoIterator<Item = &'a Signature>>(&mut self, iter: T) {
+        for signature in iter {
+            self.add(signature);
+        }
+    }
+}
+
+impl fmt::Debug for AggregateSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        amcl_utils::fmt_hex(f, &self.as_bytes())
+    }
+}
+
+// Check the inputs to verify_multiple, in order: lengths, Message sizes, then duplicate
+// Messages.
+fn check_multiple_inputs<M: AsRef<[u8]>>(msg: &[M], pubkeys: usize) -> Result<(), VerifyError> {
+    // Messages are 32 bytes and need a 1:1 ratio to PublicKeys
+    if msg.len() != pubkeys {
+        return Err(VerifyError::MismatchedLengths {
+            msgs: msg.len(),
+            pubkeys,
+        });
+    }
+    if pubkeys == 0 {
+        return Err(VerifyError::Empty);
+    }
+
+    // Messages should always be 32 bytes
+    if let Some(i) = msg.iter().position(|msg| msg.as_ref().len() != MSG_LENGTH) {
+        return Err(VerifyError::IncorrectMessageLength(i));
+    }
+
+    if let Some(i) = first_duplicate_message(msg.iter().map(|msg| msg.as_ref())) {
+        return Err(VerifyError::DuplicateMessage(i));
+    }
+    Ok(())
+}
+
+// Find the first Message which equals an earlier Message, AggregateVerify requires there are
+// none.
+fn first_duplicate_message<'a, I>(msgs: I) -> Option<usize>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    let mut msgs: Vec<(&[u8], usize)> = msgs.enumerate().map(|(i, msg)| (msg, i)).collect();
+    msgs.sort_unstable();
+    msgs.windows(2)
+        .filter(|pair| pair[0].0 == pair[1].0)
+        .map(|pair| pair[1].1)
+        .min()
+}
+
+// Convert 8 random bytes to a positive coefficient for batch verification.
+fn coefficient_from_bytes(bytes: [u8; 8]) -> BigNum {
+    let rand = u64::from_be_bytes(bytes) >> 1; // 0 <= rand <= i64::MAX
+    BigNum::new_int(rand as isize)
+}
+
+/// Verify Signatures from each of the PublicKeys over the same Message.
+///
+/// Aggregates both inputs and checks `e(S, -G1) * e(H(m), PK) == 1`. Returns false if the
+/// number of Signatures and PublicKeys differ or if there are none.
+pub fn verify_aggregate(
+    sigs: &[&Signature],
+    pubkeys: &[&PublicKey],
+    msg: &[u8],
+    domain: u64,
+) -> bool {
+    if sigs.len() != pubkeys.len() || sigs.is_empty() {
+        return false;
+    }
+    let mut agg_sig = AggregateSignature::new();
+    agg_sig.extend(sigs.iter().cloned());
+    agg_sig.verify(msg, domain, &AggregatePublicKey::from_public_keys(pubkeys))
+}
+
+/// Verify an AggregateSignature from the committee members selected by a bitfield.
+///
+/// The bitfield is interpreted as in `AggregatePublicKey::from_bitfield`. Returns false if the
+/// bitfield is malformed or selects no PublicKeys.
+pub fn verify_committee(
+    all_pubkeys: &[PublicKey],
+    bits: &[u8],
+    msg: &[u8],
+    domain: u64,
+    sig: &AggregateSignature,
+) -> bool {
+    if bits.iter().all(|byte| *byte == 0) {
+        return false;
+    }
+    match AggregatePublicKey::from_bitfield(all_pubkeys, bits) {
+        Some(agg_pub) => sig.verify(msg, domain, &agg_pub),
+        None => false,
+    }
+}
+
+/// An AggregatePublicKey and the AggregateSignature of the same PublicKeys over one Message.
+///
+/// PublicKeys and Signatures are only added together, so the two aggregates always cover the
+/// same signers.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct AggregateProof {
+    aggregate_public_key: AggregatePublicKey,
+    aggregate_signature: AggregateSignature,
+}
+
+impl AggregateProof {
+    /// Instantiate a new AggregateProof, both aggregates are set to infinity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a PublicKey and its Signature.
+    pub fn add(&mut self, pk: &PublicKey, sig: &Signature) {
+        self.aggregate_public_key.add(pk);
+        self.aggregate_signature.add(sig);
+    }
+
+    /// Verify the AggregateSignature against the AggregatePublicKey and a Message.
+    pub fn verify(&self, msg: &[u8], domain: u64) -> bool {
+        self.aggregate_signature
+            .verify(msg, domain, &self.aggregate_public_key)
+    }
+
+    pub fn aggregate_public_key(&self) -> &AggregatePublicKey {
+        &self.aggregate_public_key
+    }
+
+    pub fn aggregate_signature(&self) -> &AggregateSignature {
+        &self.aggregate_signature
+    }
+
+    /// Instantiate an AggregateProof from the 48 byte AggregatePublicKey followed by the 96 byte
+    /// AggregateSignature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AggregateProof, DecodeError> {
+        if bytes.len() != amcl_utils::MODBYTES + amcl_utils::G2_BYTE_SIZE / 2 {
+            return Err(DecodeError::IncorrectSize);
+        }
+        let (pk_bytes, sig_bytes) = bytes.split_at(amcl_utils::MODBYTES);
+        Ok(Self {
+            aggregate_public_key: AggregatePublicKey::from_bytes(pk_bytes)?,
+            aggregate_signature: AggregateSignature::from_bytes(sig_bytes)?,
+        })
+    }
+
+    /// Export the AggregateProof to compressed bytes, the AggregatePublicKey then the
+    /// AggregateSignature.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.aggregate_public_key.as_bytes();
+        bytes.extend_from_slice(&self.aggregate_signature.as_bytes());
+        bytes
+    }
+}
+
+/// An AggregateSignature which counts the Signatures added to it.
+///
+/// The count is metadata for catching programmer errors, such as aggregating the wrong number
+/// of Signatures. It is not a security guarantee: nothing binds it to the point, so a
+/// CountedAggregateSignature can claim any count for any AggregateSignature.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CountedAggregateSignature {
+    aggregate_signature: AggregateSignature,
+    count: usize,
+}
+
+impl CountedAggregateSignature {
+    /// Instantiate a new CountedAggregateSignature, the point is set to infinity and the count
+    /// to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a Signature and increment the count.
+    pub fn add(&mut self, signature: &Signature) {
+        self.aggregate_signature.add(signature);
+        self.count += 1;
+    }
+
+    /// Add another CountedAggregateSignature, the counts are summed.
+    pub fn add_aggregate(&mut self, other: &CountedAggregateSignature) {
+        self.aggregate_signature
+            .add_aggregate(&other.aggregate_signature);
+        self.count += other.count;
+    }
+
+    /// The number of Signatures added.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether no Signatures have been added.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn aggregate_signature(&self) -> &AggregateSignature {
+        &self.aggregate_signature
+    }
+
+    /// Convert into the AggregateSignature, dropping the count.
+    pub fn into_aggregate_signature(self) -> AggregateSignature {
+        self.aggregate_signature
+    }
+
+    /// Verify the AggregateSignature against an AggregatePublicKey, see
+    /// `AggregateSignature::verify`. The count is not checked.
+    pub fn verify(&self, msg: &[u8], domain: u64, avk: &AggregatePublicKey) -> bool {
+        self.aggregate_signature.verify(msg, domain, avk)
+    }
+}
+
+/// Accumulates Signatures and PublicKeys with `+=`, so one loop can build both aggregates.
+///
+/// Unlike `AggregateProof` the Signatures and PublicKeys are added independently, the number of
+/// each is tracked. The points are left projective until `finish`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SignatureAccumulator {
+    aggregate_public_key: AggregatePublicKey,
+    aggregate_signature: AggregateSignature,
+    public_keys: usize,
+    signatures: usize,
+}
+
+impl SignatureAccumulator {
+    /// Instantiate a new SignatureAccumulator, both aggregates are set to infinity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of PublicKeys added so far.
+    pub fn public_keys(&self) -> usize {
+        self.public_keys
+    }
+
+    /// The number of Signatures added so far.
+    pub fn signatures(&self) -> usize {
+        self.signatures
+    }
+
+    /// Return the AggregatePublicKey and AggregateSignature, both in affine coordinates.
+    pub fn finish(self) -> (AggregatePublicKey, AggregateSignature) {
+        let mut aggregate_public_key = self.aggregate_public_key;
+        let mut aggregate_signature = self.aggregate_signature;
+        aggregate_public_key.point.affine();
+        aggregate_signature.point.affine();
+        (aggregate_public_key, aggregate_signature)
+    }
+}
+
+impl<'a> AddAssign<&'a Signature> for SignatureAccumulator {
+    fn add_assign(&mut self, signature: &'a Signature) {
+        self.aggregate_signature.add(signature);
+        self.signatures += 1;
+    }
+}
+
+impl<'a> AddAssign<&'a PublicKey> for SignatureAccumulator {
+    fn add_assign(&mut self, public_key: &'a PublicKey) {
+        self.aggregate_public_key.add(public_key);
+        self.public_keys += 1;
+    }
+}
+
+/// A participation bitfield and the AggregateSignature of the selected committee members, as in
+/// a light client sync committee update.
+///
+/// The bitfield is interpreted as in `AggregatePublicKey::from_bitfield` against the ordered
+/// committee PublicKeys given to `verify`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CommitteeProof {
+    pub bits: Vec<u8>,
+    pub signature: AggregateSignature,
+}
+
+impl CommitteeProof {
+    /// Instantiate a CommitteeProof from a bitfield and an AggregateSignature.
+    pub fn new(bits: Vec<u8>, signature: AggregateSignature) -> Self {
+        Self { bits, signature }
+    }
+
+    /// Verify the AggregateSignature against the selected committee PublicKeys and a Message.
+    ///
+    /// See `verify_committee`.
+    pub fn verify(&self, committee: &[PublicKey], msg: &[u8], domain: u64) -> bool {
+        verify_committee(committee, &self.bits, msg, domain, &self.signature)
+    }
+
+    /// Instantiate a CommitteeProof from the 96 byte AggregateSignature followed by the bitfield.
+    pub fn from_bytes(bytes: &[u8]) -> Result<CommitteeProof, DecodeError> {
+        let sig_size = amcl_utils::G2_BYTE_SIZE / 2;
+        if bytes.len() < sig_size {
+            return Err(DecodeError::IncorrectSize);
+        }
+        let (sig_bytes, bits) = bytes.split_at(sig_size);
+        Ok(Self {
+            bits: bits.to_vec(),
+            signature: AggregateSignature::from_bytes(sig_bytes)?,
+        })
+    }
+
+    /// Export the CommitteeProof to bytes, the compressed AggregateSignature then the bitfield.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.signature.as_bytes();
+        bytes.extend_from_slice(&self.bits);
+        bytes
+    }
+}
+
+/// Incrementally aggregates PublicKeys and Signatures in lockstep for verification.
+///
+/// This is an `AggregateProof`: `add` each PublicKey with its Signature as they arrive, then
+/// `verify` the Message once.
+pub type AggregateVerifier = AggregateProof;
 
-        for (g2_point, g1_points, msgs, domain) in signature_sets {
-            if g1_points.len() != msgs.len() {
-                return false;
-            }
+/// An AggregatePublicKey prepared once for repeated verification, e.g. of a committee which is
+/// fixed for many slots.
+///
+/// The aggregate is converted to affine coordinates, the form used as a pairing input, when it
+/// is prepared rather than on every `verify`. It does not track the PublicKeys it came from, so
+/// it must be discarded and prepared again whenever the committee rotates.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PreparedAggregatePublicKey {
+    point: G1Point,
+}
 
-            let mut rand = [0 as u8; 8]; // bytes
-            rng.fill(&mut rand);
-            let rand = i64::from_be_bytes(rand).abs(); // i64 > 0
-            let rand = BigNum::new_int(rand as isize); // BigNum
+impl PreparedAggregatePublicKey {
+    /// Prepare an AggregatePublicKey for verification.
+    pub fn new(avk: &AggregatePublicKey) -> Self {
+        let mut point = avk.point.clone();
+        point.affine();
+        Self { point }
+    }
 
-            msgs.into_iter()
-                .zip(g1_points.into_iter())
-                .for_each(|(msg, g1_point)| {
-                    let mut hash_point = hash_on_g2(&msg, domain);
-                    hash_point.affine();
+    /// Verify an AggregateSignature against the prepared AggregatePublicKey and a Message.
+    ///
+    /// This is equivalent to `AggregateSignature::verify`, so returns false if the
+    /// AggregatePublicKey is the identity.
+    pub fn verify(&self, msg: &[u8], domain: u64, sig: &AggregateSignature) -> bool {
+        if self.point.is_infinity() {
+            return false;
+        }
+        let mut sig_point = sig.point.clone();
+        sig_point.affine();
+        let mut msg_hash_point = hash_on_g2(msg, domain);
+        msg_hash_point.affine();
 
-                    let mut public_key = g1_point.into_raw();
-                    public_key.mul(&rand);
-                    public_key.affine();
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
+        ate2_evaluation(
+            &sig_point.as_raw(),
+            &generator_g1_negative,
+            &msg_hash_point,
+            &self.point.as_raw(),
+        )
+    }
+}
 
-                    // Update current pairings: *= e(msg, ri * PK)
-                    pair::another(&mut r, &hash_point, &public_key);
-                });
+impl<'a> From<&'a AggregatePublicKey> for PreparedAggregatePublicKey {
+    fn from(avk: &'a AggregatePublicKey) -> Self {
+        Self::new(avk)
+    }
+}
 
-            // Multiply Signature by r and add it to final aggregate signature
-            let temp_sig = g2_point.as_raw().clone();
-            temp_sig.mul(&rand); // AggregateSignature[i] * r
-            final_agg_sig.add(&temp_sig);
+/// An AggregateSignature along with the PublicKeys and Messages it signs.
+///
+/// The PublicKey at each index signs the Message at the same index. This is the input to
+/// `AggregateSignature::verify_signature_sets`.
+#[derive(Clone)]
+pub struct SignatureSet {
+    signature: G2Point,
+    pubkeys: Vec<G1Point>,
+    messages: Vec<Vec<u8>>,
+    domain: u64,
+}
+
+impl SignatureSet {
+    /// Instantiate a SignatureSet without any PublicKeys or Messages.
+    ///
+    /// Use `with_pair` to add each PublicKey and the Message it signed.
+    pub fn new(signature: G2Point, domain: u64) -> Self {
+        Self {
+            signature,
+            pubkeys: vec![],
+            messages: vec![],
+            domain,
         }
-        final_agg_sig.affine();
+    }
 
-        // Pairing for LHS - e(S', G1)
-        let mut negative_g1 = GroupG1::generator();
-        negative_g1.neg();
-        pair::another(&mut r, &final_agg_sig, &negative_g1);
+    /// Instantiate a SignatureSet from all of its parts.
+    ///
+    /// Returns `None` if there is not exactly one Message per PublicKey.
+    pub fn from_parts(
+        signature: G2Point,
+        pubkeys: Vec<G1Point>,
+        messages: Vec<Vec<u8>>,
+        domain: u64,
+    ) -> Option<Self> {
+        if pubkeys.len() != messages.len() {
+            return None;
+        }
+        Some(Self {
+            signature,
+            pubkeys,
+            messages,
+            domain,
+        })
+    }
 
-        // Complete pairing and verify output is 1.
-        let mut v = pair::miller(&r);
-        v = pair::fexp(&v);
-        v.isunity()
+    /// Instantiate a SignatureSet from an AggregateSignature and the PublicKeys which signed it.
+    ///
+    /// Returns `None` if there is not exactly one Message per PublicKey.
+    pub fn from_aggregate(
+        signature: &AggregateSignature,
+        pubkeys: &[&PublicKey],
+        messages: &[Vec<u8>],
+        domain: u64,
+    ) -> Option<Self> {
+        Self::from_parts(
+            signature.point.clone(),
+            pubkeys.iter().map(|pk| pk.point.clone()).collect(),
+            messages.to_vec(),
+            domain,
+        )
     }
 
-    /// Instatiate an AggregateSignature from some bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Result<AggregateSignature, DecodeError> {
-        let point = G2Point::from_bytes(bytes)?;
-        Ok(Self { point })
+    /// Instantiate a SignatureSet from a single Signature and the PublicKey which signed it.
+    pub fn from_signature(
+        signature: &Signature,
+        pubkey: &PublicKey,
+        message: &[u8],
+        domain: u64,
+    ) -> Self {
+        Self::new(signature.point.clone(), domain).with_pair(pubkey.point.clone(), message.to_vec())
     }
 
-    /// Export (serialize) the AggregateSignature to bytes.
-    pub fn as_bytes(&self) -> Vec<u8> {
-        let mut clone = self.point.clone();
-        clone.as_bytes()
+    /// Add a PublicKey and the Message it signed.
+    pub fn with_pair(mut self, pubkey: G1Point, message: Vec<u8>) -> Self {
+        self.pubkeys.push(pubkey);
+        self.messages.push(message);
+        self
     }
-}
 
-impl Default for AggregateSignature {
-    fn default() -> Self {
-        Self::new()
+    // Convert to the tuple form used by `AggregateSignature::verify_multiple_signatures`.
+    fn into_tuple(self) -> (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64) {
+        (self.signature, self.pubkeys, self.messages, self.domain)
     }
 }
 
@@ -270,8 +1479,10 @@ mod tests {
     extern crate rand;
     extern crate yaml_rust;
 
+    use self::rand::seq::SliceRandom;
     use self::yaml_rust::yaml;
-    use super::super::keys::{Keypair, SecretKey};
+    use super::super::keys::Keypair;
+    use super::super::pop::pop_prove;
     use super::*;
     use std::{fs::File, io::prelude::*, path::PathBuf};
 
@@ -483,6 +1694,66 @@ mod tests {
         helper_test_aggregate_public_keys(control_kp, signing_kps, non_signing_kps);
     }
 
+    #[test]
+    fn test_aggregate_is_order_independent() {
+        let mut rng = rand::thread_rng();
+        let msg = b"cats";
+        let domain = 42;
+        let keypairs: Vec<Keypair> = (0..64).map(|_| Keypair::random(&mut rng)).collect();
+
+        let pubkey_refs: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let expected_apk = AggregatePublicKey::from_public_keys(&pubkey_refs);
+        let mut signatures: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(msg, domain, &kp.sk))
+            .collect();
+        let mut expected_sig = AggregateSignature::new();
+        for signature in &signatures {
+            expected_sig.add(signature);
+        }
+        let expected_apk_bytes = expected_apk.as_bytes();
+        let expected_sig_bytes = expected_sig.as_bytes();
+        assert!(expected_sig.verify(msg, domain, &expected_apk));
+
+        // Starting with a PublicKey and its negation, the running sum passes through infinity
+        let mut pubkeys: Vec<PublicKey> = vec![keypairs[0].pk.clone(), keypairs[0].pk.neg()];
+        pubkeys.extend(keypairs.iter().map(|kp| kp.pk.clone()));
+        let mut apk = AggregatePublicKey::new();
+        apk.add(&pubkeys[0]);
+        apk.add(&pubkeys[1]);
+        assert!(apk.point.is_infinity());
+        for pubkey in &pubkeys[2..] {
+            apk.add(pubkey);
+        }
+        assert_eq!(apk.as_bytes(), expected_apk_bytes);
+        assert_eq!(apk, expected_apk);
+
+        for _ in 0..16 {
+            pubkeys.shuffle(&mut rng);
+            signatures.shuffle(&mut rng);
+
+            // Left projective
+            let mut apk = AggregatePublicKey::new();
+            for pubkey in &pubkeys {
+                apk.add(pubkey);
+            }
+            let mut sig = AggregateSignature::new();
+            for signature in &signatures {
+                sig.add(signature);
+            }
+            assert_eq!(apk.as_bytes(), expected_apk_bytes);
+            assert_eq!(sig.as_bytes(), expected_sig_bytes);
+
+            // Converted to affine
+            apk.point.affine();
+            sig.point.affine();
+            assert_eq!(apk.as_bytes(), expected_apk_bytes);
+            assert_eq!(sig.as_bytes(), expected_sig_bytes);
+            assert_eq!(apk, expected_apk);
+            assert_eq!(sig, expected_sig);
+        }
+    }
+
     #[test]
     fn test_known_aggregate_public_keys() {
         let control_secret_key_bytes = vec![vec![
@@ -552,59 +1823,722 @@ mod tests {
     }
 
     #[test]
-    pub fn test_verify_multiple_true() {
+    pub fn test_verify_multiple_true() {
+        let domain = 45 as u64;
+        let mut msg_1: Vec<u8> = vec![111; 32];
+        let mut msg_2: Vec<u8> = vec![222; 32];
+
+        // To form first AggregatePublicKey (and sign messages)
+        let mut aggregate_signature = AggregateSignature::new();
+        let keypair_1 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_1.sk));
+        let keypair_2 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_2.sk));
+        let keypair_3 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_3.sk));
+        let apk_1 =
+            AggregatePublicKey::from_public_keys(&[&keypair_1.pk, &keypair_2.pk, &keypair_3.pk]);
+        // Verify with one AggregateSignature and Message (same functionality as AggregateSignature::verify())
+        assert!(aggregate_signature.verify_multiple(&[msg_1.clone()], domain, &[&apk_1]));
+
+        // To form second AggregatePublicKey (and sign messages)
+        let keypair_1 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_1.sk));
+        let keypair_2 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_2.sk));
+        let keypair_3 = Keypair::random(&mut rand::thread_rng());
+        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_3.sk));
+        let apk_2 =
+            AggregatePublicKey::from_public_keys(&[&keypair_1.pk, &keypair_2.pk, &keypair_3.pk]);
+
+        let apks = [&apk_1, &apk_2];
+        assert!(aggregate_signature.verify_multiple(&[msg_1, msg_2], domain, &apks));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_iter() {
+        struct Attestation {
+            root: [u8; 32],
+            apk: AggregatePublicKey,
+        }
+
+        let domain = 45 as u64;
+        let mut aggregate_signature = AggregateSignature::new();
+        let attestations: Vec<Attestation> = (0..3)
+            .map(|i| {
+                let root = [i; 32];
+                let keypair = Keypair::random(&mut rand::thread_rng());
+                aggregate_signature.add(&Signature::new(&root, domain, &keypair.sk));
+                Attestation {
+                    root,
+                    apk: AggregatePublicKey::from_public_keys(&[&keypair.pk]),
+                }
+            })
+            .collect();
+
+        // Messages are borrowed from the Attestations
+        let pairs = attestations.iter().map(|a| (&a.root[..], &a.apk));
+        assert!(aggregate_signature.verify_multiple_iter(pairs, domain));
+        let pairs = attestations.iter().map(|a| (&a.root[..], &a.apk));
+        assert!(!aggregate_signature.verify_multiple_iter(pairs, domain + 1));
+
+        // Owned Messages also work, and agree with verify_multiple
+        let msgs: Vec<Vec<u8>> = attestations.iter().map(|a| a.root.to_vec()).collect();
+        let apks: Vec<&AggregatePublicKey> = attestations.iter().map(|a| &a.apk).collect();
+        assert!(aggregate_signature.verify_multiple(&msgs, domain, &apks));
+        assert!(aggregate_signature
+            .verify_multiple_iter(msgs.iter().cloned().zip(apks.iter().cloned()), domain));
+
+        // Missing pair, duplicate Message, wrong length and no pairs
+        assert!(!aggregate_signature.verify_multiple_iter(
+            attestations[1..].iter().map(|a| (&a.root[..], &a.apk)),
+            domain
+        ));
+        let duplicate = attestations
+            .iter()
+            .map(|a| (&attestations[0].root[..], &a.apk));
+        assert!(!aggregate_signature.verify_multiple_iter(duplicate, domain));
+        let short = attestations.iter().map(|a| (&a.root[1..], &a.apk));
+        assert!(!aggregate_signature.verify_multiple_iter(short, domain));
+        let empty: Vec<(&[u8], &AggregatePublicKey)> = vec![];
+        assert!(!aggregate_signature.verify_multiple_iter(empty, domain));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_mixed() {
+        let domain = 45 as u64;
+        let msg_1: Vec<u8> = vec![111; 32];
+        let msg_2: Vec<u8> = vec![222; 32];
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+
+        // msg_1 is signed by two keys which are aggregated, msg_2 by a single key
+        let mut aggregate_signature = AggregateSignature::new();
+        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypairs[0].sk));
+        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypairs[1].sk));
+        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypairs[2].sk));
+        let apk = AggregatePublicKey::from_public_keys(&[&keypairs[0].pk, &keypairs[1].pk]);
+
+        let msgs = [msg_1.clone(), msg_2.clone()];
+        let keys: [&dyn G1Wrapper; 2] = [&apk, &keypairs[2].pk];
+        assert!(aggregate_signature.verify_multiple_mixed(&msgs, domain, &keys));
+
+        // Agrees with verify_multiple on one element aggregates
+        let apk_2 = AggregatePublicKey::from_public_keys(&[&keypairs[2].pk]);
+        assert!(aggregate_signature.verify_multiple(&msgs, domain, &[&apk, &apk_2]));
+
+        // Swapped keys, wrong domain, mismatched lengths and duplicate Messages
+        let swapped: [&dyn G1Wrapper; 2] = [&keypairs[2].pk, &apk];
+        assert!(!aggregate_signature.verify_multiple_mixed(&msgs, domain, &swapped));
+        assert!(!aggregate_signature.verify_multiple_mixed(&msgs, domain + 1, &keys));
+        assert!(!aggregate_signature.verify_multiple_mixed(&msgs, domain, &keys[..1]));
+        assert!(!aggregate_signature.verify_multiple_mixed(&[msg_1.clone(), msg_1], domain, &keys));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_duplicate_messages() {
+        let domain = 45;
+        let msg: Vec<u8> = vec![111; 32];
+        let keypair_1 = Keypair::random(&mut rand::thread_rng());
+        let keypair_2 = Keypair::random(&mut rand::thread_rng());
+        let mut aggregate_signature = AggregateSignature::new();
+        aggregate_signature.add(&Signature::new(&msg, domain, &keypair_1.sk));
+        aggregate_signature.add(&Signature::new(&msg, domain, &keypair_2.sk));
+        let apk_1 = AggregatePublicKey::from_public_keys(&[&keypair_1.pk]);
+        let apk_2 = AggregatePublicKey::from_public_keys(&[&keypair_2.pk]);
+
+        // The pairing equation holds but the Messages are not distinct
+        assert!(!aggregate_signature.verify_multiple(
+            &[msg.clone(), msg.clone()],
+            domain,
+            &[&apk_1, &apk_2]
+        ));
+        let mut root = [0; 32];
+        root.copy_from_slice(&msg);
+        assert!(!aggregate_signature.verify_multiple_signing_roots(
+            &[root, root],
+            domain,
+            &[&apk_1, &apk_2]
+        ));
+
+        // FastAggregateVerify is the path for a single Message
+        let apk = AggregatePublicKey::from_public_keys(&[&keypair_1.pk, &keypair_2.pk]);
+        assert!(aggregate_signature.verify(&msg, domain, &apk));
+    }
+
+    #[test]
+    pub fn test_try_verify_multiple() {
+        let domain = 45;
+        let msgs: Vec<Vec<u8>> = (0..3).map(|i| vec![i; 32]).collect();
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let mut aggregate_signature = AggregateSignature::new();
+        let mut apks = vec![];
+        for (msg, kp) in msgs.iter().zip(keypairs.iter()) {
+            aggregate_signature.add(&Signature::new(msg, domain, &kp.sk));
+            apks.push(AggregatePublicKey::from_public_keys(&[&kp.pk]));
+        }
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
+
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&msgs, domain, &apk_refs),
+            Ok(())
+        );
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&msgs, domain + 1, &apk_refs),
+            Err(VerifyError::InvalidSignature)
+        );
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&msgs[1..], domain, &apk_refs),
+            Err(VerifyError::MismatchedLengths {
+                msgs: 2,
+                pubkeys: 3
+            })
+        );
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&[], domain, &[]),
+            Err(VerifyError::Empty)
+        );
+
+        let mut malformed = msgs.clone();
+        malformed[1].push(0);
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&malformed, domain, &apk_refs),
+            Err(VerifyError::IncorrectMessageLength(1))
+        );
+
+        let duplicates = vec![msgs[2].clone(), msgs[0].clone(), msgs[2].clone()];
+        assert_eq!(
+            aggregate_signature.try_verify_multiple(&duplicates, domain, &apk_refs),
+            Err(VerifyError::DuplicateMessage(2))
+        );
+    }
+
+    #[test]
+    #[ignore]
+    pub fn test_verify_multiple_true_large() {
+        // Testing large number of PublicKeys
+        // Default to ignore as this takes about 10mins
+        let domain = 45;
+        let mut msg_1: Vec<u8> = vec![11; 32];
+        let mut msg_2: Vec<u8> = vec![22; 32];
+        let mut aggregate_signature = AggregateSignature::new();
+        let mut apk_1 = AggregatePublicKey::new();
+        let mut apk_2 = AggregatePublicKey::new();
+        for _ in 0..1024 {
+            let key_1 = Keypair::random(&mut rand::thread_rng());
+            let key_2 = Keypair::random(&mut rand::thread_rng());
+            apk_1.add(&key_1.pk);
+            apk_2.add(&key_2.pk);
+            aggregate_signature.add(&Signature::new(&msg_1, domain, &key_1.sk));
+            aggregate_signature.add(&Signature::new(&msg_2, domain, &key_2.sk));
+        }
+
+        assert!(aggregate_signature.verify_multiple(&[msg_1, msg_2], domain, &[&apk_1, &apk_2]));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_from_signatures() {
+        let domain = 45 as u64;
+        let msgs: Vec<Vec<u8>> = (0..3).map(|i| vec![i; 32]).collect();
+        let msg_refs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+
+        // Two signers per Message
+        let mut sigs = vec![];
+        let mut apks = vec![];
+        for msg in &msgs {
+            let mut apk = AggregatePublicKey::new();
+            for _ in 0..2 {
+                let kp = Keypair::random(&mut rand::thread_rng());
+                sigs.push(Signature::new(msg, domain, &kp.sk));
+                apk.add(&kp.pk);
+            }
+            apks.push(apk);
+        }
+        let sig_refs: Vec<&Signature> = sigs.iter().collect();
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
+
+        assert!(AggregateSignature::verify_multiple_from_signatures(
+            &sig_refs, &msg_refs, domain, &apk_refs
+        ));
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.extend(sig_refs.iter().cloned());
+        assert!(agg_sig.verify_multiple(&msgs, domain, &apk_refs));
+
+        // A missing Signature, the wrong domain, or mismatched lengths
+        assert!(!AggregateSignature::verify_multiple_from_signatures(
+            &sig_refs[1..],
+            &msg_refs,
+            domain,
+            &apk_refs
+        ));
+        assert!(!AggregateSignature::verify_multiple_from_signatures(
+            &sig_refs,
+            &msg_refs,
+            domain + 1,
+            &apk_refs
+        ));
+        assert!(!AggregateSignature::verify_multiple_from_signatures(
+            &sig_refs,
+            &msg_refs[1..],
+            domain,
+            &apk_refs
+        ));
+
+        // No Signatures for no Messages
+        assert!(AggregateSignature::verify_multiple_from_signatures(
+            &[],
+            &[],
+            domain,
+            &[]
+        ));
+    }
+
+    #[test]
+    pub fn test_empty_aggregate() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(&msg, domain, &keypair.sk);
+
+        // Zero Signatures aggregate to the identity
+        let empty_sig = AggregateSignature::new();
+        assert!(empty_sig.point.is_infinity());
+        assert_eq!(empty_sig.as_bytes(), Signature::identity().as_bytes());
+        let mut extended = AggregateSignature::new();
+        extended.extend(Vec::<Signature>::new());
+        assert_eq!(extended, empty_sig);
+
+        // AggregateVerify of no Messages accepts only the identity
+        assert!(empty_sig.verify_multiple(&[], domain, &[]));
+        assert_eq!(empty_sig.try_verify_multiple(&[], domain, &[]), Ok(()));
+        assert!(empty_sig.verify_multiple_mixed(&[], domain, &[]));
+        let mut non_empty = AggregateSignature::new();
+        non_empty.add(&signature);
+        assert!(empty_sig.verify_multiple_strict(&[], domain, &[]));
+        assert!(empty_sig.verify_multiple_signing_roots(&[], domain, &[]));
+        assert!(empty_sig.verify_multiple_iter(Vec::<(&[u8], &AggregatePublicKey)>::new(), domain));
+        assert!(empty_sig.aggregate_verify_grouped(&[], &[], domain));
+        assert!(!non_empty.verify_multiple(&[], domain, &[]));
+        assert!(!non_empty.verify_multiple_mixed(&[], domain, &[]));
+        assert!(!non_empty.verify_multiple_strict(&[], domain, &[]));
+        assert!(!non_empty.verify_multiple_signing_roots(&[], domain, &[]));
+        assert!(!non_empty.verify_multiple_iter(Vec::<(&[u8], &AggregatePublicKey)>::new(), domain));
+        assert!(!non_empty.aggregate_verify_grouped(&[], &[], domain));
+
+        // A Message never verifies against zero PublicKeys
+        let empty_pub_key = AggregatePublicKey::from_public_keys(&[]);
+        assert!(empty_pub_key.point.is_infinity());
+        assert!(!empty_sig.verify(&msg, domain, &empty_pub_key));
+        assert!(!non_empty.verify(&msg, domain, &empty_pub_key));
+        assert!(!verify_aggregate(&[], &[], &msg, domain));
+        assert!(!verify_committee(
+            &[keypair.pk.clone()],
+            &[0],
+            &msg,
+            domain,
+            &empty_sig
+        ));
+
+        // The identity Signature against the identity PublicKey
+        let identity = Signature::identity();
+        let identity_pk = PublicKey::new_from_raw(&GroupG1::new());
+        assert!(!identity.verify(&msg, domain, &identity_pk));
+        assert!(!verify_aggregate(
+            &[&identity],
+            &[&identity_pk],
+            &msg,
+            domain
+        ));
+        let identity_apk = AggregatePublicKey::from_public_keys(&[&identity_pk]);
+        assert!(!empty_sig.verify_multiple(&[msg.clone()], domain, &[&identity_apk]));
+
+        // Every other entry point rejects the identity PublicKey too
+        let neg_g1 = PublicKey::negative_generator().point;
+        assert!(!PreparedAggregatePublicKey::new(&identity_apk).verify(&msg, domain, &empty_sig));
+        assert!(!empty_sig.verify_with_neg_generator(&msg, domain, &identity_apk, &neg_g1));
+        assert!(!empty_sig.verify_single_key_multi_message(
+            &[msg.as_slice()],
+            domain,
+            &identity_pk
+        ));
+        assert!(!identity.verify_hashed(&[1; 48], &[2; 48], &identity_pk));
+        let identity_triple: (&PublicKey, &[u8], u64, &Signature) =
+            (&identity_pk, &msg, domain, &identity);
+        assert_eq!(
+            Signature::verify_batch(&mut rand::thread_rng(), &[identity_triple]),
+            vec![false]
+        );
+        let identity_sets = vec![(
+            identity.point.clone(),
+            vec![identity_pk.point.clone()],
+            vec![msg.clone()],
+            domain,
+        )];
+        assert!(!AggregateSignature::verify_multiple_signatures(
+            &mut rand::thread_rng(),
+            identity_sets.iter().cloned()
+        ));
+        assert!(
+            !AggregateSignature::verify_multiple_signatures_deterministic(
+                identity_sets.iter().cloned()
+            )
+        );
+        #[cfg(feature = "rayon")]
+        {
+            let pool = ::rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap();
+            assert!(!AggregateSignature::verify_multiple_signatures_with_pool(
+                &pool,
+                &mut rand::thread_rng(),
+                identity_sets.iter().cloned()
+            ));
+        }
+    }
+
+    #[test]
+    pub fn test_verify_aggregate() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let sigs: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+        let sig_refs: Vec<&Signature> = sigs.iter().collect();
+        let pk_refs: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+
+        assert!(verify_aggregate(&sig_refs, &pk_refs, &msg, domain));
+        assert!(!verify_aggregate(&sig_refs, &pk_refs, &msg, domain + 1));
+        assert!(!verify_aggregate(&sig_refs, &pk_refs, &[2; 32], domain));
+        assert!(!verify_aggregate(&sig_refs[1..], &pk_refs, &msg, domain));
+        assert!(!verify_aggregate(
+            &sig_refs[1..],
+            &pk_refs[..3],
+            &msg,
+            domain
+        ));
+        assert!(!verify_aggregate(&[], &[], &msg, domain));
+    }
+
+    #[test]
+    pub fn test_from_secret_keys() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+        let sks: Vec<SecretKey> = (0..5)
+            .map(|_| SecretKey::random(&mut rand::thread_rng()))
+            .collect();
+        let sk_refs: Vec<&SecretKey> = sks.iter().collect();
+        let pks: Vec<PublicKey> = sks.iter().map(PublicKey::from_secret_key).collect();
+        let pk_refs: Vec<&PublicKey> = pks.iter().collect();
+
+        let agg_pub_key = AggregatePublicKey::from_secret_keys(&sk_refs);
+        assert_eq!(agg_pub_key, AggregatePublicKey::from_public_keys(&pk_refs));
+
+        let mut agg_sig = AggregateSignature::new();
+        for sk in &sks {
+            agg_sig.add(&Signature::new(&msg, domain, sk));
+        }
+        assert!(agg_sig.verify(&msg, domain, &agg_pub_key));
+
+        assert_eq!(
+            AggregatePublicKey::from_secret_keys(&[]),
+            AggregatePublicKey::new()
+        );
+    }
+
+    #[test]
+    pub fn test_counted_aggregate_signature() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+
+        let mut counted = CountedAggregateSignature::new();
+        assert!(counted.is_empty());
+        let mut agg_sig = AggregateSignature::new();
+        let mut agg_pub_key = AggregatePublicKey::new();
+        for _ in 0..3 {
+            let kp = Keypair::random(&mut rand::thread_rng());
+            let sig = Signature::new(&msg, domain, &kp.sk);
+            counted.add(&sig);
+            agg_sig.add(&sig);
+            agg_pub_key.add(&kp.pk);
+        }
+        assert_eq!(counted.len(), 3);
+        assert!(!counted.is_empty());
+        assert_eq!(*counted.aggregate_signature(), agg_sig);
+        assert!(counted.verify(&msg, domain, &agg_pub_key));
+
+        let mut other = CountedAggregateSignature::new();
+        let kp = Keypair::random(&mut rand::thread_rng());
+        other.add(&Signature::new(&msg, domain, &kp.sk));
+        counted.add_aggregate(&other);
+        agg_pub_key.add(&kp.pk);
+        assert_eq!(counted.len(), 4);
+        assert!(counted.verify(&msg, domain, &agg_pub_key));
+
+        // The identity is counted even though the point is unchanged
+        let before = counted.aggregate_signature().clone();
+        counted.add(&Signature::identity());
+        assert_eq!(counted.len(), 5);
+        assert_eq!(counted.into_aggregate_signature(), before);
+    }
+
+    #[test]
+    pub fn test_signature_accumulator() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+
+        let mut accumulator = SignatureAccumulator::new();
+        let mut agg_pub_key = AggregatePublicKey::new();
+        let mut agg_sig = AggregateSignature::new();
+        for _ in 0..4 {
+            let kp = Keypair::random(&mut rand::thread_rng());
+            let sig = Signature::new(&msg, domain, &kp.sk);
+            accumulator += &kp.pk;
+            accumulator += &sig;
+            agg_pub_key.add(&kp.pk);
+            agg_sig.add(&sig);
+        }
+        accumulator += &Keypair::random(&mut rand::thread_rng()).pk;
+        assert_eq!(accumulator.public_keys(), 5);
+        assert_eq!(accumulator.signatures(), 4);
+
+        let (finished_pub_key, finished_sig) = accumulator.finish();
+        assert_eq!(finished_sig, agg_sig);
+        assert_ne!(finished_pub_key, agg_pub_key);
+        assert!(!finished_sig.verify(&msg, domain, &finished_pub_key));
+        assert!(agg_sig.verify(&msg, domain, &agg_pub_key));
+
+        let (empty_pub_key, empty_sig) = SignatureAccumulator::new().finish();
+        assert_eq!(empty_pub_key, AggregatePublicKey::new());
+        assert_eq!(empty_sig, AggregateSignature::new());
+    }
+
+    #[test]
+    pub fn test_aggregate_proof() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+
+        let mut proof = AggregateProof::new();
+        for _ in 0..4 {
+            let kp = Keypair::random(&mut rand::thread_rng());
+            proof.add(&kp.pk, &Signature::new(&msg, domain, &kp.sk));
+        }
+        assert!(proof.verify(&msg, domain));
+        assert!(!proof.verify(&[2; 32], domain));
+        assert!(!proof.verify(&msg, domain + 1));
+
+        let bytes = proof.as_bytes();
+        assert_eq!(bytes.len(), 48 + 96);
+        let decoded = AggregateProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(&msg, domain));
+        assert_eq!(
+            AggregateProof::from_bytes(&bytes[1..]),
+            Err(DecodeError::IncorrectSize)
+        );
+
+        // A Signature from a different key breaks the proof
+        let kp = Keypair::random(&mut rand::thread_rng());
+        let other = Keypair::random(&mut rand::thread_rng());
+        proof.add(&kp.pk, &Signature::new(&msg, domain, &other.sk));
+        assert!(!proof.verify(&msg, domain));
+    }
+
+    #[test]
+    pub fn test_try_from_public_keys() {
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        assert_eq!(
+            AggregatePublicKey::try_from_public_keys(&pks),
+            Ok(AggregatePublicKey::from_public_keys(&pks))
+        );
+
+        let infinity = PublicKey::new_from_raw(&GroupG1::new());
+        assert_eq!(
+            AggregatePublicKey::try_from_public_keys(&[pks[0], pks[1], &infinity]),
+            Err((2, DecodeError::PointAtInfinityDisallowed))
+        );
+
+        // (4, y) is on the curve but not in the subgroup
+        let not_in_subgroup = PublicKey::from_bytes_unchecked(&hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap()).unwrap();
+        assert_eq!(
+            AggregatePublicKey::try_from_public_keys(&[pks[0], &not_in_subgroup, &infinity]),
+            Err((1, DecodeError::NotInSubgroup))
+        );
+    }
+
+    #[test]
+    pub fn test_aggregate_public_key_subtract() {
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let mut apk = AggregatePublicKey::from_public_keys(&[
+            &keypairs[0].pk,
+            &keypairs[1].pk,
+            &keypairs[2].pk,
+        ]);
+        apk.subtract(&keypairs[1].pk);
+        assert_eq!(
+            apk,
+            AggregatePublicKey::from_public_keys(&[&keypairs[0].pk, &keypairs[2].pk])
+        );
+
+        apk.subtract(&keypairs[0].pk);
+        apk.subtract(&keypairs[2].pk);
+        assert!(apk.point.is_infinity());
+    }
+
+    #[test]
+    pub fn test_verify_with_neg_generator() {
+        let domain = 45 as u64;
+        let msg = vec![7; 32];
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let mut aggregate_signature = AggregateSignature::new();
+        for kp in &keypairs {
+            aggregate_signature.add(&Signature::new(&msg, domain, &kp.sk));
+        }
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let apk = AggregatePublicKey::from_public_keys(&pks);
+
+        let neg_g1 = PublicKey::negative_generator().point;
+        assert!(aggregate_signature.verify_with_neg_generator(&msg, domain, &apk, &neg_g1));
+        assert!(!aggregate_signature.verify_with_neg_generator(&msg, domain + 1, &apk, &neg_g1));
+
+        // The positive generator and infinity are rejected
+        let g1 = PublicKey::generator().point;
+        assert!(!aggregate_signature.verify_with_neg_generator(&msg, domain, &apk, &g1));
+        assert!(!aggregate_signature.verify_with_neg_generator(
+            &msg,
+            domain,
+            &apk,
+            &G1Point::new()
+        ));
+    }
+
+    #[test]
+    pub fn test_aggregate_verifier() {
+        let domain = 45 as u64;
+        let msg = vec![42; 32];
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+
+        let mut verifier = AggregateVerifier::new();
+        for kp in &keypairs {
+            verifier.add(&kp.pk, &Signature::new(&msg, domain, &kp.sk));
+        }
+        assert!(verifier.verify(&msg, domain));
+        assert!(!verifier.verify(&msg, domain + 1));
+
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        assert_eq!(
+            *verifier.aggregate_public_key(),
+            AggregatePublicKey::from_public_keys(&pks)
+        );
+    }
+
+    #[test]
+    pub fn test_prepared_aggregate_public_key() {
+        let domain = 45 as u64;
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let avk = AggregatePublicKey::from_public_keys(&pks);
+        let prepared = PreparedAggregatePublicKey::new(&avk);
+
+        // The same committee signs in successive slots
+        for slot in 0..3 {
+            let msg = vec![slot; 32];
+            let mut agg_sig = AggregateSignature::new();
+            for kp in &keypairs {
+                agg_sig.add(&Signature::new(&msg, domain, &kp.sk));
+            }
+            assert!(prepared.verify(&msg, domain, &agg_sig));
+            assert!(!prepared.verify(&msg, domain + 1, &agg_sig));
+            assert!(!prepared.verify(&[slot + 1; 32], domain, &agg_sig));
+        }
+
+        // A rotated committee needs a new PreparedAggregatePublicKey
+        let msg = vec![42; 32];
+        let mut agg_sig = AggregateSignature::new();
+        for kp in &keypairs[1..] {
+            agg_sig.add(&Signature::new(&msg, domain, &kp.sk));
+        }
+        assert!(!prepared.verify(&msg, domain, &agg_sig));
+        let rotated =
+            PreparedAggregatePublicKey::from(&AggregatePublicKey::from_public_keys(&pks[1..]));
+        assert!(rotated.verify(&msg, domain, &agg_sig));
+    }
+
+    #[test]
+    pub fn test_verify_signing_roots() {
         let domain = 45 as u64;
-        let mut msg_1: Vec<u8> = vec![111; 32];
-        let mut msg_2: Vec<u8> = vec![222; 32];
+        let roots: Vec<[u8; 32]> = (0..3).map(|i| [i; 32]).collect();
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
 
-        // To form first AggregatePublicKey (and sign messages)
         let mut aggregate_signature = AggregateSignature::new();
-        let keypair_1 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_1.sk));
-        let keypair_2 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_2.sk));
-        let keypair_3 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_1, domain, &keypair_3.sk));
-        let apk_1 =
-            AggregatePublicKey::from_public_keys(&[&keypair_1.pk, &keypair_2.pk, &keypair_3.pk]);
-        // Verify with one AggregateSignature and Message (same functionality as AggregateSignature::verify())
-        assert!(aggregate_signature.verify_multiple(&[msg_1.clone()], domain, &[&apk_1]));
+        let mut apks = vec![];
+        for (root, kp) in roots.iter().zip(keypairs.iter()) {
+            aggregate_signature.add(&Signature::new(root, domain, &kp.sk));
+            apks.push(AggregatePublicKey::from_public_keys(&[&kp.pk]));
+        }
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
 
-        // To form second AggregatePublicKey (and sign messages)
-        let keypair_1 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_1.sk));
-        let keypair_2 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_2.sk));
-        let keypair_3 = Keypair::random(&mut rand::thread_rng());
-        aggregate_signature.add(&Signature::new(&msg_2, domain, &keypair_3.sk));
-        let apk_2 =
-            AggregatePublicKey::from_public_keys(&[&keypair_1.pk, &keypair_2.pk, &keypair_3.pk]);
+        assert!(aggregate_signature.verify_multiple_signing_roots(&roots, domain, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_signing_roots(&roots, domain + 1, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_signing_roots(&roots[1..], domain, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_signing_roots(&[], domain, &[]));
 
-        let apks = [&apk_1, &apk_2];
-        assert!(aggregate_signature.verify_multiple(&[msg_1, msg_2], domain, &apks));
+        // Same Message
+        let mut aggregate_signature = AggregateSignature::new();
+        for kp in &keypairs {
+            aggregate_signature.add(&Signature::new(&roots[0], domain, &kp.sk));
+        }
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let apk = AggregatePublicKey::from_public_keys(&pks);
+        assert!(aggregate_signature.verify_signing_root(&roots[0], domain, &apk));
+        assert!(!aggregate_signature.verify_signing_root(&roots[1], domain, &apk));
     }
 
     #[test]
-    #[ignore]
-    pub fn test_verify_multiple_true_large() {
-        // Testing large number of PublicKeys
-        // Default to ignore as this takes about 10mins
-        let domain = 45;
-        let mut msg_1: Vec<u8> = vec![11; 32];
-        let mut msg_2: Vec<u8> = vec![22; 32];
+    pub fn test_verify_multiple_strict() {
+        let domain = 45 as u64;
+        let msgs: Vec<Vec<u8>> = (0..3).map(|i| vec![i; 32]).collect();
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+
         let mut aggregate_signature = AggregateSignature::new();
-        let mut apk_1 = AggregatePublicKey::new();
-        let mut apk_2 = AggregatePublicKey::new();
-        for _ in 0..1024 {
-            let key_1 = Keypair::random(&mut rand::thread_rng());
-            let key_2 = Keypair::random(&mut rand::thread_rng());
-            apk_1.add(&key_1.pk);
-            apk_2.add(&key_2.pk);
-            aggregate_signature.add(&Signature::new(&msg_1, domain, &key_1.sk));
-            aggregate_signature.add(&Signature::new(&msg_2, domain, &key_2.sk));
+        let mut apks = vec![];
+        for (msg, kp) in msgs.iter().zip(keypairs.iter()) {
+            aggregate_signature.add(&Signature::new(msg, domain, &kp.sk));
+            apks.push(AggregatePublicKey::from_public_keys(&[&kp.pk]));
         }
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
 
-        assert!(aggregate_signature.verify_multiple(&[msg_1, msg_2], domain, &[&apk_1, &apk_2]));
+        assert!(aggregate_signature.verify_multiple_strict(&msgs, domain, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_strict(&msgs, domain + 1, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_strict(&msgs[1..], domain, &apk_refs));
+        assert!(!aggregate_signature.verify_multiple_strict(&[], domain, &[]));
+
+        // Malformed last Message
+        let mut malformed = msgs.clone();
+        malformed[2].push(0);
+        assert!(!aggregate_signature.verify_multiple_strict(&malformed, domain, &apk_refs));
     }
 
     #[test]
@@ -816,9 +2750,6 @@ mod tests {
 
         add_aggregate_signature.add_aggregate(&aggregate_signature34);
 
-        add_aggregate_signature.point.affine();
-        aggregate_signature.point.affine();
-
         assert_eq!(add_aggregate_signature, aggregate_signature);
         assert!(add_aggregate_signature.verify(&msg, domain, &aggregate_public_key));
     }
@@ -862,4 +2793,666 @@ mod tests {
 
         assert!(valid);
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    pub fn test_aggregate_par() {
+        let msg = vec![1; 32];
+        let domain = 42;
+        let keypairs: Vec<Keypair> = (0..100)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+        let pk_refs: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let sig_refs: Vec<&Signature> = signatures.iter().collect();
+
+        let mut agg_sig = AggregateSignature::new();
+        for signature in &signatures {
+            agg_sig.add(signature);
+        }
+        let agg_pub_key = AggregatePublicKey::from_public_keys(&pk_refs);
+
+        let par_pub_key = AggregatePublicKey::from_public_keys_par(&pk_refs);
+        let par_sig = AggregateSignature::from_signatures_par(&sig_refs);
+        assert_eq!(par_pub_key, agg_pub_key);
+        assert_eq!(par_sig, agg_sig);
+        assert!(par_sig.verify(&msg, domain, &par_pub_key));
+
+        for len in 0..4 {
+            assert_eq!(
+                AggregatePublicKey::from_public_keys_par(&pk_refs[..len]),
+                AggregatePublicKey::from_public_keys(&pk_refs[..len])
+            );
+        }
+        assert_eq!(
+            AggregateSignature::from_signatures_par(&[]),
+            AggregateSignature::new()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    pub fn test_verify_multiple_signatures_with_pool() {
+        let mut rng = &mut rand::thread_rng();
+        let domain: u64 = 1;
+        let pool = ::rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let mut signature_sets = vec![];
+        for i in 0..4 {
+            let keypairs: Vec<Keypair> = (0..3).map(|_| Keypair::random(&mut rng)).collect();
+            let msgs: Vec<Vec<u8>> = (0..3).map(|j| vec![(i * 3 + j) as u8; 32]).collect();
+            let mut aggregate_signature = AggregateSignature::new();
+            for (keypair, msg) in keypairs.iter().zip(msgs.iter()) {
+                aggregate_signature.add(&Signature::new(msg, domain, &keypair.sk));
+            }
+            let public_keys = keypairs.iter().map(|kp| kp.pk.point.clone()).collect();
+            signature_sets.push((aggregate_signature.point, public_keys, msgs, domain));
+        }
+
+        assert!(AggregateSignature::verify_multiple_signatures_with_pool(
+            &pool,
+            &mut rng,
+            signature_sets.iter().cloned()
+        ));
+
+        // A Message swapped between sets
+        let mut invalid_sets = signature_sets.clone();
+        let msg = invalid_sets[0].2[0].clone();
+        invalid_sets[0].2[0] = invalid_sets[1].2[0].clone();
+        invalid_sets[1].2[0] = msg;
+        assert!(!AggregateSignature::verify_multiple_signatures_with_pool(
+            &pool,
+            &mut rng,
+            invalid_sets.into_iter()
+        ));
+
+        // Mismatched PublicKeys and Messages
+        let mut invalid_sets = signature_sets.clone();
+        invalid_sets[2].1.pop();
+        assert!(!AggregateSignature::verify_multiple_signatures_with_pool(
+            &pool,
+            &mut rng,
+            invalid_sets.into_iter()
+        ));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_signatures_aggregated() {
+        let mut rng = &mut rand::thread_rng();
+        let domain: u64 = 1;
+
+        // Each set has two Messages each signed by three PublicKeys
+        let mut signature_sets = vec![];
+        for i in 0..3 {
+            let mut aggregate_signature = AggregateSignature::new();
+            let mut pairs = vec![];
+            for j in 0..2 {
+                let msg = vec![(i * 2 + j) as u8; 32];
+                let mut apk = AggregatePublicKey::new();
+                for _ in 0..3 {
+                    let keypair = Keypair::random(&mut rng);
+                    aggregate_signature.add(&Signature::new(&msg, domain, &keypair.sk));
+                    apk.add(&keypair.pk);
+                }
+                pairs.push((msg, apk));
+            }
+            signature_sets.push((aggregate_signature.point, pairs, domain));
+        }
+
+        assert!(AggregateSignature::verify_multiple_signatures_aggregated(
+            &mut rng,
+            signature_sets.iter().cloned()
+        ));
+
+        // An AggregatePublicKey moved to the wrong Message
+        let mut invalid_sets = signature_sets.clone();
+        let apk = invalid_sets[1].1[0].1.clone();
+        invalid_sets[1].1[0].1 = invalid_sets[1].1[1].1.clone();
+        invalid_sets[1].1[1].1 = apk;
+        assert!(!AggregateSignature::verify_multiple_signatures_aggregated(
+            &mut rng,
+            invalid_sets.into_iter()
+        ));
+    }
+
+    #[test]
+    pub fn test_verify_multiple_signatures_deterministic() {
+        let domain: u64 = 1;
+        let keypairs: Vec<Keypair> = (0..6)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+
+        let mut sets = vec![];
+        for (i, pair) in keypairs.chunks(2).enumerate() {
+            let msgs = vec![vec![i as u8; 32], vec![i as u8 + 100; 32]];
+            let mut agg_sig = AggregateSignature::new();
+            agg_sig.add(&Signature::new(&msgs[0], domain, &pair[0].sk));
+            agg_sig.add(&Signature::new(&msgs[1], domain, &pair[1].sk));
+            let pubkeys = vec![pair[0].pk.point.clone(), pair[1].pk.point.clone()];
+            sets.push((agg_sig.point, pubkeys, msgs, domain));
+        }
+
+        assert!(
+            AggregateSignature::verify_multiple_signatures_deterministic(sets.clone().into_iter())
+        );
+        assert!(
+            AggregateSignature::verify_multiple_signatures_deterministic(sets.clone().into_iter())
+        );
+
+        // Swap two Signatures, each set is invalid but the sum is unchanged
+        let mut invalid_sets = sets.clone();
+        let first = invalid_sets[0].0.clone();
+        invalid_sets[0].0 = invalid_sets[1].0.clone();
+        invalid_sets[1].0 = first;
+        assert!(
+            !AggregateSignature::verify_multiple_signatures_deterministic(invalid_sets.into_iter())
+        );
+
+        // Wrong domain
+        let mut invalid_sets = sets.clone();
+        invalid_sets[2].3 = domain + 1;
+        assert!(
+            !AggregateSignature::verify_multiple_signatures_deterministic(invalid_sets.into_iter())
+        );
+    }
+
+    #[test]
+    pub fn test_find_invalid_signatures() {
+        let mut rng = &mut rand::thread_rng();
+        let domain: u64 = 1;
+        let n = 10;
+        let m = 2;
+        let invalid_indices = vec![2, 7];
+
+        let mut signature_sets = vec![];
+        for i in 0..n {
+            let mut aggregate_signature = AggregateSignature::new();
+            let mut public_keys = vec![];
+            let mut msgs = vec![];
+            for j in 0..m {
+                let msg = vec![(j * i) as u8; 32];
+                let keypair = Keypair::random(&mut rng);
+                aggregate_signature.add(&Signature::new(&msg, domain, &keypair.sk));
+                public_keys.push(keypair.pk.point);
+                msgs.push(msg);
+            }
+
+            // Sign a different message for the sets which should be found
+            if invalid_indices.contains(&i) {
+                msgs[0] = vec![255; 32];
+            }
+
+            signature_sets.push((aggregate_signature.point, public_keys, msgs, domain));
+        }
+
+        assert_eq!(
+            AggregateSignature::find_invalid_signatures(&mut rng, &signature_sets),
+            invalid_indices
+        );
+
+        // A valid batch has no invalid sets
+        let valid_sets: Vec<_> = signature_sets
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !invalid_indices.contains(i))
+            .map(|(_, set)| set)
+            .collect();
+        assert!(AggregateSignature::find_invalid_signatures(&mut rng, &valid_sets).is_empty());
+    }
+
+    #[test]
+    pub fn test_from_weighted() {
+        let domain = 45 as u64;
+        let msg: Vec<u8> = vec![1; 32];
+
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+        let weights = [1, 32, 0, u64::max_value()];
+
+        let weighted_keys: Vec<(&PublicKey, u64)> = keypairs
+            .iter()
+            .zip(weights.iter())
+            .map(|(kp, weight)| (&kp.pk, *weight))
+            .collect();
+        let weighted_sigs: Vec<(&Signature, u64)> = signatures
+            .iter()
+            .zip(weights.iter())
+            .map(|(sig, weight)| (sig, *weight))
+            .collect();
+
+        let agg_pub = AggregatePublicKey::from_weighted(&weighted_keys);
+        let agg_sig = AggregateSignature::from_weighted(&weighted_sigs);
+        assert!(agg_sig.verify(&msg, domain, &agg_pub));
+
+        // Weights matter, the unweighted aggregate must not verify
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let unweighted_pub = AggregatePublicKey::from_public_keys(&pks);
+        assert!(!agg_sig.verify(&msg, domain, &unweighted_pub));
+
+        // Unit weights are the same as plain aggregation
+        let unit_keys: Vec<(&PublicKey, u64)> = pks.iter().map(|pk| (*pk, 1)).collect();
+        assert_eq!(
+            AggregatePublicKey::from_weighted(&unit_keys),
+            unweighted_pub
+        );
+    }
+
+    #[test]
+    pub fn test_fast_aggregate_verify_pop() {
+        let domain = 45 as u64;
+        let msg = vec![1; 32];
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pops: Vec<Signature> = keypairs.iter().map(|kp| pop_prove(&kp.sk)).collect();
+
+        let mut agg_sig = AggregateSignature::new();
+        for kp in &keypairs {
+            agg_sig.add(&Signature::new(&msg, domain, &kp.sk));
+        }
+
+        let mut keys_with_pops: Vec<(&PublicKey, &Signature)> = keypairs
+            .iter()
+            .zip(pops.iter())
+            .map(|(kp, pop)| (&kp.pk, pop))
+            .collect();
+        assert!(agg_sig.fast_aggregate_verify_pop(&msg, domain, &keys_with_pops));
+        assert!(!agg_sig.fast_aggregate_verify_pop(&msg, domain + 1, &keys_with_pops));
+        assert!(!agg_sig.fast_aggregate_verify_pop(&msg, domain, &[]));
+
+        // One swapped proof fails even though the signature is valid
+        keys_with_pops[0].1 = &pops[1];
+        assert!(!agg_sig.fast_aggregate_verify_pop(&msg, domain, &keys_with_pops));
+    }
+
+    #[test]
+    pub fn test_try_add_identity() {
+        let domain = 45;
+        let msg: Vec<u8> = vec![111; 32];
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(&msg, domain, &keypair.sk);
+
+        let mut agg_sig = AggregateSignature::new();
+        assert_eq!(agg_sig.try_add(&signature), Ok(()));
+        assert_eq!(
+            agg_sig.try_add(&Signature::identity()),
+            Err(DecodeError::PointAtInfinityDisallowed)
+        );
+        let apk = AggregatePublicKey::from_public_keys(&[&keypair.pk]);
+        assert!(agg_sig.verify(&msg, domain, &apk));
+
+        // add accepts the identity and the aggregate is unchanged, so the contribution is not
+        // detectable from the result
+        let mut free_rider = AggregateSignature::new();
+        free_rider.add(&signature);
+        free_rider.add(&Signature::identity());
+        assert_eq!(free_rider.as_bytes(), agg_sig.as_bytes());
+    }
+
+    #[test]
+    pub fn test_try_add_unique() {
+        let domain = 45;
+        let msg: Vec<u8> = vec![111; 32];
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let sigs: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut agg_sig = AggregateSignature::new();
+        for sig in &sigs {
+            assert!(agg_sig.try_add_unique(sig, &mut seen));
+        }
+
+        // The double sign is caught and the aggregate still verifies
+        assert!(!agg_sig.try_add_unique(&sigs[1], &mut seen));
+        let pks: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let apk = AggregatePublicKey::from_public_keys(&pks);
+        assert!(agg_sig.verify(&msg, domain, &apk));
+    }
+
+    #[test]
+    pub fn test_aggregate_verify_grouped() {
+        let domain = 45;
+        let msg_1: Vec<u8> = vec![1; 32];
+        let msg_2: Vec<u8> = vec![2; 32];
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pubkeys: Vec<&PublicKey> = keypairs.iter().map(|keypair| &keypair.pk).collect();
+        let msgs: Vec<&[u8]> = vec![&msg_1[..], &msg_2[..], &msg_1[..], &msg_1[..]];
+
+        let mut agg_sig = AggregateSignature::new();
+        for (keypair, msg) in keypairs.iter().zip(msgs.iter()) {
+            agg_sig.add(&Signature::new(msg, domain, &keypair.sk));
+        }
+        assert!(agg_sig.aggregate_verify_grouped(&pubkeys, &msgs, domain));
+
+        // The flat inputs repeat a Message so are rejected by verify_multiple
+        let apks: Vec<AggregatePublicKey> = pubkeys
+            .iter()
+            .map(|pk| AggregatePublicKey::from_public_keys(&[*pk]))
+            .collect();
+        let apk_refs: Vec<&AggregatePublicKey> = apks.iter().collect();
+        let msg_vecs: Vec<Vec<u8>> = msgs.iter().map(|msg| msg.to_vec()).collect();
+        assert!(!agg_sig.verify_multiple(&msg_vecs, domain, &apk_refs));
+
+        // Swapped Messages, wrong domain, mismatched lengths and no inputs
+        let swapped: Vec<&[u8]> = vec![&msg_2[..], &msg_1[..], &msg_1[..], &msg_1[..]];
+        assert!(!agg_sig.aggregate_verify_grouped(&pubkeys, &swapped, domain));
+        assert!(!agg_sig.aggregate_verify_grouped(&pubkeys, &msgs, domain + 1));
+        assert!(!agg_sig.aggregate_verify_grouped(&pubkeys[1..], &msgs, domain));
+        assert!(!agg_sig.aggregate_verify_grouped(&[], &[], domain));
+        assert!(AggregateSignature::new().aggregate_verify_grouped(&[], &[], domain));
+    }
+
+    #[test]
+    pub fn test_verify_single_key_multi_message() {
+        let domain = 45 as u64;
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msgs: Vec<Vec<u8>> = (0..4).map(|i| vec![i; 32]).collect();
+        let msg_refs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+
+        let mut agg_sig = AggregateSignature::new();
+        for msg in &msgs {
+            agg_sig.add(&Signature::new(msg, domain, &keypair.sk));
+        }
+        assert!(agg_sig.verify_single_key_multi_message(&msg_refs, domain, &keypair.pk));
+
+        // Missing message, wrong domain, wrong key and no messages
+        assert!(!agg_sig.verify_single_key_multi_message(&msg_refs[1..], domain, &keypair.pk));
+        assert!(!agg_sig.verify_single_key_multi_message(&msg_refs, domain + 1, &keypair.pk));
+        let other = Keypair::random(&mut rand::thread_rng());
+        assert!(!agg_sig.verify_single_key_multi_message(&msg_refs, domain, &other.pk));
+        assert!(!AggregateSignature::new().verify_single_key_multi_message(
+            &[],
+            domain,
+            &keypair.pk
+        ));
+    }
+
+    #[test]
+    pub fn test_from_bitfield() {
+        let domain = 45 as u64;
+        let msg: Vec<u8> = vec![1; 32];
+
+        let keypairs: Vec<Keypair> = (0..10)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pubkeys: Vec<PublicKey> = keypairs.iter().map(|kp| kp.pk.clone()).collect();
+
+        // Participants 0, 3 and 9
+        let bits = [0b0000_1001, 0b0000_0010];
+        let agg_pub = AggregatePublicKey::from_bitfield(&pubkeys, &bits).unwrap();
+        let expected =
+            AggregatePublicKey::from_public_keys(&[&pubkeys[0], &pubkeys[3], &pubkeys[9]]);
+        assert_eq!(agg_pub, expected);
+
+        let mut agg_sig = AggregateSignature::new();
+        for i in &[0, 3, 9] {
+            agg_sig.add(&Signature::new(&msg, domain, &keypairs[*i].sk));
+        }
+        assert!(agg_sig.verify(&msg, domain, &agg_pub));
+
+        // No participants
+        assert_eq!(
+            AggregatePublicKey::from_bitfield(&pubkeys, &[0, 0]).unwrap(),
+            AggregatePublicKey::new()
+        );
+
+        // Wrong length
+        assert!(AggregatePublicKey::from_bitfield(&pubkeys, &[0xff]).is_none());
+        assert!(AggregatePublicKey::from_bitfield(&pubkeys, &[0xff, 0x03, 0]).is_none());
+
+        // Padding bit set
+        assert!(AggregatePublicKey::from_bitfield(&pubkeys, &[0xff, 0x07]).is_none());
+    }
+
+    #[test]
+    pub fn test_committee_proof() {
+        let domain = 45 as u64;
+        let msg: Vec<u8> = vec![1; 32];
+        let keypairs: Vec<Keypair> = (0..10)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let committee: Vec<PublicKey> = keypairs.iter().map(|kp| kp.pk.clone()).collect();
+
+        // Participants 1, 2 and 8
+        let mut agg_sig = AggregateSignature::new();
+        for i in &[1, 2, 8] {
+            agg_sig.add(&Signature::new(&msg, domain, &keypairs[*i].sk));
+        }
+        let proof = CommitteeProof::new(vec![0b0000_0110, 0b0000_0001], agg_sig);
+        assert!(proof.verify(&committee, &msg, domain));
+        assert!(!proof.verify(&committee, &msg, domain + 1));
+        assert!(!proof.verify(&committee[..8], &msg, domain));
+
+        // Round trip through bytes
+        let bytes = proof.as_bytes();
+        assert_eq!(bytes.len(), 96 + 2);
+        let decoded = CommitteeProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(&committee, &msg, domain));
+
+        assert_eq!(
+            CommitteeProof::from_bytes(&bytes[..95]),
+            Err(DecodeError::IncorrectSize)
+        );
+    }
+
+    #[test]
+    pub fn test_verify_committee() {
+        let domain = 45 as u64;
+        let msg: Vec<u8> = vec![1; 32];
+
+        let keypairs: Vec<Keypair> = (0..10)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pubkeys: Vec<PublicKey> = keypairs.iter().map(|kp| kp.pk.clone()).collect();
+
+        // Participants 0, 3 and 9
+        let bits = [0b0000_1001, 0b0000_0010];
+        let mut agg_sig = AggregateSignature::new();
+        for i in &[0, 3, 9] {
+            agg_sig.add(&Signature::new(&msg, domain, &keypairs[*i].sk));
+        }
+        assert!(verify_committee(&pubkeys, &bits, &msg, domain, &agg_sig));
+        assert!(!verify_committee(
+            &pubkeys,
+            &bits,
+            &msg,
+            domain + 1,
+            &agg_sig
+        ));
+        assert!(!verify_committee(
+            &pubkeys,
+            &[0b0000_1001, 0],
+            &msg,
+            domain,
+            &agg_sig
+        ));
+
+        // Malformed bitfield
+        assert!(!verify_committee(
+            &pubkeys,
+            &bits[..1],
+            &msg,
+            domain,
+            &agg_sig
+        ));
+        assert!(!verify_committee(
+            &pubkeys,
+            &[0b0000_1001, 0b0000_0110],
+            &msg,
+            domain,
+            &agg_sig
+        ));
+
+        // No participants, even though the empty aggregates satisfy the pairing equation
+        assert!(!verify_committee(
+            &pubkeys,
+            &[0, 0],
+            &msg,
+            domain,
+            &AggregateSignature::new()
+        ));
+    }
+
+    #[test]
+    pub fn test_verify_signature_sets() {
+        let mut rng = &mut rand::thread_rng();
+        let domain: u64 = 1;
+        let n = 10;
+        let m = 3;
+
+        let mut signature_sets = vec![];
+        for i in 0..n {
+            let keypairs: Vec<Keypair> = (0..m).map(|_| Keypair::random(&mut rng)).collect();
+            let msgs: Vec<Vec<u8>> = (0..m).map(|j| vec![(j * i) as u8; 32]).collect();
+
+            let mut aggregate_signature = AggregateSignature::new();
+            for (keypair, msg) in keypairs.iter().zip(msgs.iter()) {
+                aggregate_signature.add(&Signature::new(msg, domain, &keypair.sk));
+            }
+
+            let set = keypairs.into_iter().zip(msgs.into_iter()).fold(
+                SignatureSet::new(aggregate_signature.point, domain),
+                |set, (keypair, msg)| set.with_pair(keypair.pk.point, msg),
+            );
+            signature_sets.push(set);
+        }
+
+        assert!(AggregateSignature::verify_signature_sets(
+            &mut rng,
+            &signature_sets
+        ));
+
+        // Sets with the wrong domain fail
+        signature_sets[3].domain = 2;
+        assert!(!AggregateSignature::verify_signature_sets(
+            &mut rng,
+            &signature_sets
+        ));
+    }
+
+    #[test]
+    pub fn test_signature_set_from_parts() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = vec![1; 32];
+        let sig = Signature::new(&msg, 1, &keypair.sk);
+
+        assert!(SignatureSet::from_parts(
+            sig.point.clone(),
+            vec![keypair.pk.point.clone()],
+            vec![msg.clone()],
+            1
+        )
+        .is_some());
+        assert!(SignatureSet::from_parts(
+            sig.point.clone(),
+            vec![keypair.pk.point.clone()],
+            vec![msg.clone(), msg],
+            1
+        )
+        .is_none());
+        assert!(SignatureSet::from_parts(sig.point, vec![], vec![vec![1; 32]], 1).is_none());
+    }
+
+    #[test]
+    pub fn test_signature_set_from_aggregate() {
+        let domain: u64 = 1;
+        let msgs = vec![vec![1; 32], vec![2; 32]];
+        let keypairs: Vec<Keypair> = (0..2)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let pubkeys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+
+        let mut aggregate_signature = AggregateSignature::new();
+        for (keypair, msg) in keypairs.iter().zip(msgs.iter()) {
+            aggregate_signature.add(&Signature::new(msg, domain, &keypair.sk));
+        }
+
+        let single_keypair = Keypair::random(&mut rand::thread_rng());
+        let single_signature = Signature::new(&msgs[0], domain, &single_keypair.sk);
+
+        let signature_sets = vec![
+            SignatureSet::from_aggregate(&aggregate_signature, &pubkeys, &msgs, domain).unwrap(),
+            SignatureSet::from_signature(&single_signature, &single_keypair.pk, &msgs[0], domain),
+        ];
+        assert!(AggregateSignature::verify_signature_sets(
+            &mut rand::thread_rng(),
+            &signature_sets
+        ));
+
+        assert!(
+            SignatureSet::from_aggregate(&aggregate_signature, &pubkeys, &msgs[..1], domain)
+                .is_none()
+        );
+    }
+
+    #[test]
+    pub fn test_extend() {
+        let domain = 45 as u64;
+        let msg: Vec<u8> = vec![1; 32];
+        let keypairs: Vec<Keypair> = (0..4)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+
+        // Extend by reference in two batches
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.extend(&signatures[..2]);
+        agg_sig.extend(signatures[2..].iter());
+        let mut agg_pub = AggregatePublicKey::new();
+        agg_pub.extend(keypairs.iter().map(|kp| &kp.pk));
+        assert!(agg_sig.verify(&msg, domain, &agg_pub));
+
+        // Extend by value
+        let mut owned_agg_sig = AggregateSignature::new();
+        owned_agg_sig.extend(signatures);
+        let mut owned_agg_pub = AggregatePublicKey::new();
+        owned_agg_pub.extend(keypairs.into_iter().map(|kp| kp.pk));
+        assert!(owned_agg_sig.verify(&msg, domain, &owned_agg_pub));
+        assert_eq!(owned_agg_sig.as_bytes(), agg_sig.as_bytes());
+        assert_eq!(owned_agg_pub.as_bytes(), agg_pub.as_bytes());
+    }
+
+    #[test]
+    pub fn test_aggregate_write_read() {
+        let msg: Vec<u8> = vec![1; 32];
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.add(&Signature::new(&msg, 1, &keypair.sk));
+        let mut agg_pub = AggregatePublicKey::new();
+        agg_pub.add(&keypair.pk);
+
+        let mut buffer = vec![];
+        agg_pub.write_to(&mut buffer).unwrap();
+        agg_sig.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 48 + 96);
+
+        let mut reader = buffer.as_slice();
+        let read_pub = AggregatePublicKey::read_from(&mut reader).unwrap();
+        let read_sig = AggregateSignature::read_from(&mut reader).unwrap();
+        assert_eq!(read_pub.as_bytes(), agg_pub.as_bytes());
+        assert_eq!(read_sig.as_bytes(), agg_sig.as_bytes());
+        assert!(read_sig.verify(&msg, 1, &read_pub));
+    }
 }
@@ -3,14 +3,23 @@ extern crate amcl;
 extern crate hex;
 extern crate rand;
 extern crate ring;
+extern crate zeroize;
 
 use self::amcl::arch::Chunk;
-use self::ring::digest::{digest, SHA256};
+use self::ring::digest::{digest, Context, SHA256};
+#[cfg(feature = "std")]
+use self::zeroize::Zeroize;
 use super::errors::DecodeError;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
 use BLSCurve::big::BIG;
 use BLSCurve::big::{MODBYTES as bls381_MODBYTES, NLEN};
+use BLSCurve::dbig::DBIG;
 use BLSCurve::ecp::ECP;
 use BLSCurve::ecp2::ECP2;
+use BLSCurve::fp::FP as bls381_FP;
 use BLSCurve::fp12::FP12 as bls381_FP12;
 use BLSCurve::fp2::FP2 as bls381_FP2;
 use BLSCurve::pair::{ate, ate2, fexp};
@@ -19,6 +28,7 @@ use BLSCurve::rom;
 pub type BigNum = BIG;
 pub type GroupG1 = ECP;
 pub type GroupG2 = ECP2;
+pub type FP = bls381_FP;
 pub type FP2 = bls381_FP2;
 pub type FP12 = bls381_FP12;
 
@@ -29,6 +39,8 @@ pub const MODBYTES: usize = bls381_MODBYTES;
 pub const G1_BYTE_SIZE: usize = (2 * MODBYTES) as usize;
 // Byte size of element in group G2
 pub const G2_BYTE_SIZE: usize = (4 * MODBYTES) as usize;
+// Byte size of element in group GT (twelve field elements)
+pub const GT_BYTE_SIZE: usize = (12 * MODBYTES) as usize;
 // Byte size of secret key
 pub const MOD_BYTE_SIZE: usize = bls381_MODBYTES;
 
@@ -61,26 +73,1071 @@ pub const G2_COFACTOR_SHIFT: [Chunk; NLEN] = [
     0x0000_0000_0000_0000,
 ];
 
+// Effective G2 cofactor h_eff from RFC 9380 section 8.8.2 as arrays of i64,
+// h_eff = G2_H_EFF_HIGH * G2_H_EFF_SHIFT + G2_H_EFF_LOW
+pub const G2_H_EFF_HIGH: [Chunk; NLEN] = [
+    0x029C_2F17_8731_DB95,
+    0x01BF_C0C5_423F_F84C,
+    0x028E_9145_AD76_8998,
+    0x003A_A46C_D4A2_238A,
+    0x00F2_EE75_B358_4C6A,
+    0x0000_0000_02F1_A7C2,
+    0x0000_0000_0000_0000,
+];
+pub const G2_H_EFF_SHIFT: [Chunk; NLEN] = [
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_4000_0000,
+    0x0000_0000_0000_0000,
+];
+pub const G2_H_EFF_LOW: [Chunk; NLEN] = [
+    0x0002_0005_AAA9_5551,
+    0x0253_02B7_AEFD_AD3A,
+    0x00BC_0668_9F6A_3598,
+    0x01A7_5D1D_F06B_A553,
+    0x015D_1212_B02E_C0EC,
+    0x0000_0000_1B60_AFC0,
+    0x0000_0000_0000_0000,
+];
+
+// Fixed-base multiplication of the G1 generator uses 4 bit windows over a 256 bit scalar.
+pub const G1_FIXED_BASE_WINDOW_BITS: usize = 4;
+pub const G1_FIXED_BASE_WINDOWS: usize = 256 / G1_FIXED_BASE_WINDOW_BITS;
+
+// Byte size of a SHA256 digest and of a SHA256 input block
+pub const SHA256_BYTES: usize = 32;
+pub const SHA256_BLOCK_BYTES: usize = 64;
+// Bytes hashed per field element in hash_to_field, ceil((ceil(log2(q)) + 128) / 8)
+pub const HASH_TO_FIELD_BYTES: usize = 64;
+
+// The simplified SWU map for G2 works on the curve E2': y^2 = x^3 + 240i * x + 1012(1 + i)
+// which is 3-isogenous to E2, the isogeny is x = x_num / x_den and y = y * y_num / y_den.
+// 3-isogeny x numerator coefficients as (real, imaginary) from the constant term
+pub const ISO3_XNUM: [[[Chunk; NLEN]; 2]; 4] = [
+    [
+        [
+            0x0238_AAAA_AAAA_97D6,
+            0x018E_38D0_F671_C718,
+            0x0042_3C50_AE15_D5C2,
+            0x00E7_F4E8_10AA_22D6,
+            0x0247_D7ED_8532_C52D,
+            0x03A3_8CCF_AED6_DEA6,
+            0x0000_0000_5C75_9507,
+        ],
+        [
+            0x0238_AAAA_AAAA_97D6,
+            0x018E_38D0_F671_C718,
+            0x0042_3C50_AE15_D5C2,
+            0x00E7_F4E8_10AA_22D6,
+            0x0247_D7ED_8532_C52D,
+            0x03A3_8CCF_AED6_DEA6,
+            0x0000_0000_5C75_9507,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x02A9_FFFF_FFFF_C71A,
+            0x00AA_AA72_E355_5549,
+            0x00C6_B4F2_0A41_8147,
+            0x02B7_DEB8_31FE_6882,
+            0x02D7_87C8_8F98_4F87,
+            0x02EA_A66F_0C84_9BF3,
+            0x0000_0001_1560_BF17,
+        ],
+    ],
+    [
+        [
+            0x02A9_FFFF_FFFF_C71E,
+            0x00AA_AA72_E355_5549,
+            0x00C6_B4F2_0A41_8147,
+            0x02B7_DEB8_31FE_6882,
+            0x02D7_87C8_8F98_4F87,
+            0x02EA_A66F_0C84_9BF3,
+            0x0000_0001_1560_BF17,
+        ],
+        [
+            0x0354_FFFF_FFFF_E38D,
+            0x0255_5539_71AA_AAA4,
+            0x0063_5A79_0520_C0A3,
+            0x035B_EF5C_18FF_3441,
+            0x036B_C3E4_47CC_27C3,
+            0x0375_5337_8642_4DF9,
+            0x0000_0000_8AB0_5F8B,
+        ],
+    ],
+    [
+        [
+            0x00E2_AAAA_AAAA_5ED1,
+            0x0238_E343_D9C7_1C62,
+            0x0108_F142_B857_5709,
+            0x039F_D3A0_42A8_8B58,
+            0x011F_5FB6_14CB_14B4,
+            0x028E_333E_BB5B_7A9A,
+            0x0000_0001_71D6_541F,
+        ],
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+    ],
+];
+// 3-isogeny x denominator coefficients as (real, imaginary) from the constant term
+pub const ISO3_XDEN: [[[Chunk; NLEN]; 2]; 3] = [
+    [
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x01FE_FFFF_FFFF_AA63,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_000C,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x01FE_FFFF_FFFF_AA9F,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0001,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+    ],
+];
+// 3-isogeny y numerator coefficients as (real, imaginary) from the constant term
+pub const ISO3_YNUM: [[[Chunk; NLEN]; 2]; 4] = [
+    [
+        [
+            0x02CF_C71C_71C6_D706,
+            0x0309_7AFE_324B_DA04,
+            0x039D_87D2_7E50_0FC8,
+            0x0352_81FD_926F_D510,
+            0x0307_6D11_930F_7DA5,
+            0x02AD_044E_D669_3062,
+            0x0000_0001_5304_77C7,
+        ],
+        [
+            0x02CF_C71C_71C6_D706,
+            0x0309_7AFE_324B_DA04,
+            0x039D_87D2_7E50_0FC8,
+            0x0352_81FD_926F_D510,
+            0x0307_6D11_930F_7DA5,
+            0x02AD_044E_D669_3062,
+            0x0000_0001_5304_77C7,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x0238_AAAA_AAAA_97BE,
+            0x018E_38D0_F671_C718,
+            0x0042_3C50_AE15_D5C2,
+            0x00E7_F4E8_10AA_22D6,
+            0x0247_D7ED_8532_C52D,
+            0x03A3_8CCF_AED6_DEA6,
+            0x0000_0000_5C75_9507,
+        ],
+    ],
+    [
+        [
+            0x02A9_FFFF_FFFF_C71C,
+            0x00AA_AA72_E355_5549,
+            0x00C6_B4F2_0A41_8147,
+            0x02B7_DEB8_31FE_6882,
+            0x02D7_87C8_8F98_4F87,
+            0x02EA_A66F_0C84_9BF3,
+            0x0000_0001_1560_BF17,
+        ],
+        [
+            0x0354_FFFF_FFFF_E38F,
+            0x0255_5539_71AA_AAA4,
+            0x0063_5A79_0520_C0A3,
+            0x035B_EF5C_18FF_3441,
+            0x036B_C3E4_47CC_27C3,
+            0x0375_5337_8642_4DF9,
+            0x0000_0000_8AB0_5F8B,
+        ],
+    ],
+    [
+        [
+            0x01B3_71C7_1C71_8B10,
+            0x0242_5E95_B712_F678,
+            0x037C_69AA_2745_24E7,
+            0x00DE_8789_8A1A_C3A5,
+            0x01E3_811A_D076_1B0F,
+            0x02DB_3DE6_FEFD_C10F,
+            0x0000_0001_24C9_AD43,
+        ],
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+    ],
+];
+// 3-isogeny y denominator coefficients as (real, imaginary) from the constant term
+pub const ISO3_YDEN: [[[Chunk; NLEN]; 2]; 4] = [
+    [
+        [
+            0x01FE_FFFF_FFFF_A8FB,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+        [
+            0x01FE_FFFF_FFFF_A8FB,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x01FE_FFFF_FFFF_A9D3,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0012,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x01FE_FFFF_FFFF_AA99,
+            0x02FF_FFAC_54FF_FFEE,
+            0x012A_0F6B_0F62_41EA,
+            0x0213_CE14_4AFD_9CC3,
+            0x0243_4BAC_D764_774B,
+            0x025F_F9A6_92C6_E9ED,
+            0x0000_0001_A011_1EA3,
+        ],
+    ],
+    [
+        [
+            0x0000_0000_0000_0001,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+        [
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+            0x0000_0000_0000_0000,
+        ],
+    ],
+];
+
+// h_eff for clearing the cofactor of E1, 1 - x where x is the curve parameter
+pub const G1_H_EFF: [Chunk; NLEN] = [
+    0x0201_0000_0001_0001,
+    0x0000_0000_0000_0034,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+];
+
+// The simplified SWU map for G1 works on the curve E1': y^2 = x^3 + A' * x + B'
+// which is 11-isogenous to E1, the isogeny is x = x_num / x_den and y = y * y_num / y_den.
+// A' of E1'
+pub const ISO11_A: [Chunk; NLEN] = [
+    0x00F4_2808_2D58_4C1D,
+    0x00DB_E368_383E_5FD7,
+    0x0181_AEFD_881A_C989,
+    0x014E_0FB9_9AA3_63A2,
+    0x02C9_6D49_82B0_EA98,
+    0x00EE_3A50_CF5A_4E80,
+    0x0000_0000_0144_698A,
+];
+
+// B' of E1'
+pub const ISO11_B: [Chunk; NLEN] = [
+    0x01CC_48E9_8E17_2BE0,
+    0x00C8_568C_5B3A_A974,
+    0x0014_FCEF_35EF_55A2,
+    0x03C3_C93D_01C2_82E7,
+    0x0075_3EEE_3B20_16C1,
+    0x005A_200C_0062_C4BA,
+    0x0000_0001_2E29_08D1,
+];
+
+// 11-isogeny x numerator coefficients from the constant term
+pub const ISO11_XNUM: [[Chunk; NLEN]; 12] = [
+    [
+        0x02AC_1662_7346_49B7,
+        0x0030_B57C_B98B_5BAB,
+        0x03B5_6CDB_4E2C_8561,
+        0x0222_8B5C_017F_C989,
+        0x01D9_9815_856B_303E,
+        0x03A0_CCD0_2E02_4407,
+        0x0000_0001_1A05_F2B1,
+    ],
+    [
+        0x0034_EEF1_B3CB_83BB,
+        0x023C_A9BC_C630_D5BA,
+        0x0233_C70D_1E86_B483,
+        0x016C_BDAA_105F_D597,
+        0x0221_47A8_1C7C_17E7,
+        0x0250_EACB_C162_2EAC,
+        0x0000_0001_7294_ED3E,
+    ],
+    [
+        0x0017_9F9D_AC9E_DCB0,
+        0x030F_8F4A_825C_A7F8,
+        0x0250_1EC6_8E25_C958,
+        0x01CC_A566_0F95_A1E3,
+        0x01D1_0A9A_1BCE_0324,
+        0x025D_9E3B_0744_1231,
+        0x0000_0000_D540_05DB,
+    ],
+    [
+        0x01B3_8864_1D9B_6861,
+        0x01B8_9738_C41C_64F1,
+        0x0328_9F1B_3308_3533,
+        0x0195_AA36_FC97_C6CC,
+        0x0307_E554_12D7_F5E4,
+        0x03F3_1B6D_D381_8274,
+        0x0000_0001_778E_7166,
+    ],
+    [
+        0x0115_4CE9_AC88_95D9,
+        0x028A_1BCC_079D_F114,
+        0x02B6_5982_FAC1_8985,
+        0x0168_495F_ECFC_21BB,
+        0x03E4_118E_5499_DB99,
+        0x0066_7D10_D990_AD2C,
+        0x0000_0000_E997_26A3,
+    ],
+    [
+        0x0113_C1C6_6F65_2983,
+        0x01C3_4B72_B9CF_4673,
+        0x02B9_097E_68F9_0A08,
+        0x01F7_6549_E66E_7B4E,
+        0x03F7_A74A_B5DB_3CB1,
+        0x035C_C4FF_C074_4806,
+        0x0000_0001_630C_3250,
+    ],
+    [
+        0x01D7_F225_A139_ED84,
+        0x0094_4A30_414B_B2B7,
+        0x0221_8F9C_86B2_A8DA,
+        0x0099_3C3E_3386_4023,
+        0x038A_E652_BFB1_1586,
+        0x03F9_134A_5A8D_C9B0,
+        0x0000_0000_D6ED_6553,
+    ],
+    [
+        0x00CB_5618_E3F0_C88E,
+        0x01F2_3E32_3D1D_6BE7,
+        0x0062_EF0F_2753_339B,
+        0x02AC_9D6D_36C6_9A0B,
+        0x00D1_117E_5335_6DE5,
+        0x006A_F6F8_BA1D_0E21,
+        0x0000_0001_7B81_E770,
+    ],
+    [
+        0x0171_986A_8497_E317,
+        0x00A5_7CA5_ADD3_A55B,
+        0x016C_928C_5D1D_E4FA,
+        0x01B3_9E7D_55D2_8B16,
+        0x0163_BE99_0DC4_3B75,
+        0x0269_E3F1_1EE4_2CCD,
+        0x0000_0000_80D3_CF1F,
+    ],
+    [
+        0x0324_1067_BE39_0C9E,
+        0x0242_CBB7_00C9_DE5F,
+        0x014B_AF4B_B1B7_FA31,
+        0x0200_E831_7265_9D8C,
+        0x015D_138F_22DD_2ECB,
+        0x02F3_E9F1_0B83_0DD4,
+        0x0000_0001_69B1_F8E1,
+    ],
+    [
+        0x0267_DF3F_1605_FB7B,
+        0x02DD_C7E3_0A17_7B32,
+        0x0336_003B_1486_6F69,
+        0x0377_99E1_FE5B_542B,
+        0x01D2_565B_0DFA_7DCC,
+        0x0273_81F8_9CB6_3B02,
+        0x0000_0001_0321_DA07,
+    ],
+    [
+        0x01C8_BA2E_8BA2_D229,
+        0x02C6_E02D_934E_47EA,
+        0x03F1_BC24_C6B6_8C24,
+        0x01F8_8B20_DEF0_8F02,
+        0x0381_EDEE_3D31_D79D,
+        0x0389_839C_2F47_A588,
+        0x0000_0000_6E08_C248,
+    ],
+];
+
+// 11-isogeny x denominator coefficients from the constant term
+pub const ISO11_XDEN: [[Chunk; NLEN]; 11] = [
+    [
+        0x013C_F9FA_40D2_1B1C,
+        0x0235_A06F_8D0F_7E26,
+        0x0086_17FC_8AC6_2B55,
+        0x012E_8D6D_22EA_7256,
+        0x034B_D3FA_6F01_D5EF,
+        0x033F_C66B_862C_B98B,
+        0x0000_0000_8CA8_D548,
+    ],
+    [
+        0x01C8_276E_C82B_3BFF,
+        0x02AA_211B_2C09_BA79,
+        0x0025_88C4_8BF5_713D,
+        0x0328_33C2_0030_049B,
+        0x0298_E536_3670_41E8,
+        0x02D5_6710_D22D_1C44,
+        0x0000_0001_2561_A5DE,
+    ],
+    [
+        0x00C2_39BA_5CB8_3E19,
+        0x00F4_259F_253F_B73F,
+        0x00E0_0B11_ACEA_CD6A,
+        0x01BD_69C6_3347_F299,
+        0x01BF_F299_1F6F_8941,
+        0x01E8_C897_A04D_F98A,
+        0x0000_0000_B296_2FE5,
+    ],
+    [
+        0x030D_E893_8DC6_2CD8,
+        0x01B5_490F_BB3D_7104,
+        0x028A_BC28_D6FD_0497,
+        0x00FC_5AC5_9545_5332,
+        0x037C_40EB_545B_0824,
+        0x0162_B8BF_B20E_ABFB,
+        0x0000_0000_3425_581A,
+    ],
+    [
+        0x039D_395B_3532_A21E,
+        0x00A6_EA07_CD5E_0754,
+        0x004E_833B_306D_A9BD,
+        0x0166_8481_8AEE_35AD,
+        0x0343_E7A0_7DFF_DFC7,
+        0x008A_452A_029B_C757,
+        0x0000_0001_3A8E_1620,
+    ],
+    [
+        0x002D_F9A2_9F63_04A5,
+        0x0034_92F1_08A3_C470,
+        0x03CE_F24B_8982_F740,
+        0x03A7_3A72_B534_290E,
+        0x0305_06C6_E939_5735,
+        0x0139_99EE_554E_43DF,
+        0x0000_0000_E735_5F8E,
+    ],
+    [
+        0x0025_7449_6EE8_4A3A,
+        0x00EC_D4E3_C378_1B3B,
+        0x0073_062A_EDE9_CEA7,
+        0x0266_BD4E_8625_38B8,
+        0x03E0_5967_2157_0F57,
+        0x005A_4D86_43CF_8318,
+        0x0000_0000_772C_AACF,
+    ],
+    [
+        0x01F7_D99B_BDCC_5A5E,
+        0x016E_5227_4478_B4C4,
+        0x021C_DF98_22C5_80FA,
+        0x0308_6F29_A2A0_665B,
+        0x0074_CF01_996E_7F63,
+        0x0359_2A2C_8C2C_FD6C,
+        0x0000_0001_4A7A_C2A9,
+    ],
+    [
+        0x0376_EC3A_79A1_D641,
+        0x0099_A4AA_EE90_DC11,
+        0x00DA_67F3_9883_5038,
+        0x0075_C584_D9AD_D040,
+        0x01AF_C7A3_CCE0_7F8D,
+        0x0369_53E0_97A4_82CF,
+        0x0000_0000_A10E_CF6A,
+    ],
+    [
+        0x0163_84D1_68EC_DD0A,
+        0x01D3_92D2_DE19_400B,
+        0x0133_978F_31C1_5931,
+        0x03BA_5BDF_40DD_DB7D,
+        0x02B3_A566_80F6_82B4,
+        0x027A_4AB5_11DB_5B8F,
+        0x0000_0000_95FC_13AB,
+    ],
+    [
+        0x0000_0000_0000_0001,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+];
+
+// 11-isogeny y numerator coefficients from the constant term
+pub const ISO11_YNUM: [[Chunk; NLEN]; 16] = [
+    [
+        0x0298_4571_9707_BB33,
+        0x031E_BBA6_CEE8_F0AF,
+        0x02F6_C956_543D_3CD0,
+        0x0239_22A1_A548_AD4A,
+        0x0149_80DC_FA11_AD13,
+        0x02E8_93B8_0967_47C2,
+        0x0000_0000_90D9_7C81,
+    ],
+    [
+        0x0097_E75A_2E41_C696,
+        0x0159_C465_8BEA_2FF8,
+        0x0234_3EB6_7AD3_4D6C,
+        0x01B0_953C_E0F4_3E41,
+        0x0376_FB46_8312_23E9,
+        0x013B_9604_7544_0DB5,
+        0x0000_0001_3499_6A10,
+    ],
+    [
+        0x00DF_E240_C72D_E1F6,
+        0x0354_858A_2C01_48EE,
+        0x03E4_B914_00DA_7D26,
+        0x0359_628C_738B_0D12,
+        0x006A_3B49_9425_52E2,
+        0x02A5_9B99_BD28_E132,
+        0x0000_0000_0CC7_86BA,
+    ],
+    [
+        0x0235_5C77_B0E5_F4CB,
+        0x0016_AEA7_B187_7B29,
+        0x023E_C032_51CF_9DE4,
+        0x02E4_3BAD_E470_2792,
+        0x02D8_7467_57D4_2AA7,
+        0x0226_0708_5E26_1D46,
+        0x0000_0000_1F86_376E,
+    ],
+    [
+        0x01B6_DAEC_F2E8_FEDB,
+        0x01FE_3702_6410_2A10,
+        0x03FD_2213_51AD_C2EE,
+        0x03EF_8F39_42E1_E60C,
+        0x02A2_1529_C419_5536,
+        0x03F8_3FC4_D72B_D3F8,
+        0x0000_0000_8CC0_3FDE,
+    ],
+    [
+        0x01B2_3AB1_3633_A5F0,
+        0x03D8_C9B2_56A0_1CA6,
+        0x01C3_D3AD_5544_E203,
+        0x0352_BEB6_DEF5_D941,
+        0x01B8_F0A6_A074_A7D0,
+        0x0018_D2DA_8884_7847,
+        0x0000_0001_6603_FCA4,
+    ],
+    [
+        0x0161_F885_5FE9_D6F2,
+        0x021E_B091_83D0_57B2,
+        0x0013_C4D6_34F3_747A,
+        0x0328_AF86_132D_48C5,
+        0x0027_796B_3CE7_5BB8,
+        0x03EB_06EF_2CB2_5DF4,
+        0x0000_0000_4AB0_B9BC,
+    ],
+    [
+        0x015E_4CA3_1870_FB29,
+        0x0191_543F_B7FA_4D68,
+        0x00DA_6C26_C842_642F,
+        0x02FF_8EF7_607F_F40E,
+        0x012C_A6C6_7417_0A05,
+        0x00CE_AE1B_F7A6_49AF,
+        0x0000_0000_987C_8D53,
+    ],
+    [
+        0x0037_0E57_7BDB_A587,
+        0x0194_8071_E181_E8D8,
+        0x02E6_A1F2_0CAB_E69D,
+        0x0059_9E77_09B0_7A2D,
+        0x021E_4DA1_BB8F_3ABD,
+        0x0365_9A12_FA23_2788,
+        0x0000_0000_9FC4_018B,
+    ],
+    [
+        0x03AF_AAEB_CA73_1C30,
+        0x03DC_1577_53AE_9BCA,
+        0x01E7_ED1E_4D43_B9B3,
+        0x029E_456B_DBF8_1A61,
+        0x03AD_A14A_23C4_2A0C,
+        0x0061_AF6D_488E_AF79,
+        0x0000_0000_E1BB_A7A1,
+    ],
+    [
+        0x0013_711A_D011_C132,
+        0x03CE_9733_8FEE_BF3A,
+        0x03E4_1638_9E61_031B,
+        0x032D_B2BD_24FF_4460,
+        0x031D_43FB_93CD_2FCB,
+        0x00DF_346F_837F_42E3,
+        0x0000_0001_9713_E479,
+    ],
+    [
+        0x0207_C8A4_D007_4D8E,
+        0x0273_7D06_D135_81B3,
+        0x03E7_F911_F643_249D,
+        0x02E2_ABC3_0918_B9AF,
+        0x03FE_D2ED_CC52_3559,
+        0x03CD_BDB7_AE46_3050,
+        0x0000_0001_8B46_A908,
+    ],
+    [
+        0x014C_04F0_0B97_1EF8,
+        0x0214_7064_6484_7C83,
+        0x010E_807B_4633_F06C,
+        0x00A8_D09A_C23B_009C,
+        0x0004_F53F_447A_A7B1,
+        0x0006_E4E6_7455_4258,
+        0x0000_0000_B182_CAC1,
+    ],
+    [
+        0x02D9_D3F5_DB98_0133,
+        0x03E4_2B47_08CA_9910,
+        0x0232_D3C4_0659_CC6C,
+        0x0020_3530_5600_4F99,
+        0x027B_E315_DC75_7B3B,
+        0x0347_B2A6_DCBF_002B,
+        0x0000_0000_245A_394A,
+    ],
+    [
+        0x026B_1E71_5475_224B,
+        0x0041_26D9_5E6B_EDE1,
+        0x00F5_D396_A7CE_46BA,
+        0x0207_5FA1_95A3_66AC,
+        0x0348_C4A3_FC5E_673D,
+        0x0391_33C4_40A8_567D,
+        0x0000_0000_5C12_9645,
+    ],
+    [
+        0x00B4_56BE_69C8_B604,
+        0x0140_9FBF_B007_1DC1,
+        0x014F_A95A_F01B_2B66,
+        0x023E_1259_68E5_5EB7,
+        0x0342_DF2E_B5CB_181D,
+        0x0243_C0F3_93A9_42CE,
+        0x0000_0001_5E6B_E4E9,
+    ],
+];
+
+// 11-isogeny y denominator coefficients from the constant term
+pub const ISO11_YDEN: [[Chunk; NLEN]; 16] = [
+    [
+        0x0147_9253_B036_63C1,
+        0x00DA_23BD_8308_1B40,
+        0x0232_B5BE_72E7_A07F,
+        0x0395_E260_2F9B_BB0C,
+        0x00FA_D0EA_E960_1A6D,
+        0x02A7_262C_9486_0450,
+        0x0000_0001_6112_C4C3,
+    ],
+    [
+        0x02F6_102C_2E49_A03D,
+        0x0109_81D8_D4A7_8D4C,
+        0x0035_6F45_3E01_F78A,
+        0x03DC_C713_5672_9284,
+        0x0043_C348_B885_C84F,
+        0x00E0_4807_8683_2F5B,
+        0x0000_0001_962D_75C2,
+    ],
+    [
+        0x0225_38B5_3DBF_67F2,
+        0x015F_358D_BE5B_E247,
+        0x025D_D279_CD2E_CA67,
+        0x0155_46B9_FCC4_30D6,
+        0x016E_8EB1_5778_C485,
+        0x0190_3689_DBEA_AB9F,
+        0x0000_0000_58DF_3306,
+    ],
+    [
+        0x00D2_6D98_445F_5416,
+        0x00D9_3CB0_A0A5_EB6A,
+        0x0248_9E72_6AF4_1727,
+        0x036F_76F3_4C38_48F6,
+        0x0389_EDB4_D1D1_15C5,
+        0x0263_94E5_7C83_48EF,
+        0x0000_0001_6B7D_2887,
+    ],
+    [
+        0x0239_1423_11A5_001D,
+        0x02C5_7703_F4BB_7B76,
+        0x01A0_FC9D_EC91_6A20,
+        0x027C_3DA6_EEC1_50BB,
+        0x02F8_228D_DCC6_D19C,
+        0x0117_D0F9_2C03_3244,
+        0x0000_0000_BE0E_0795,
+    ],
+    [
+        0x02C6_477F_AAF9_B7AC,
+        0x00E3_6E77_EA73_3880,
+        0x0187_B6F0_F5A6_449F,
+        0x0319_5543_6207_17B3,
+        0x02AC_7831_82B7_0152,
+        0x0061_B6CB_67EC_99BA,
+        0x0000_0000_8D9E_5297,
+    ],
+    [
+        0x011A_1399_126A_775C,
+        0x02A7_0069_62C7_EE4F,
+        0x025B_C400_A005_1D5F,
+        0x03EA_3433_E3BD_774D,
+        0x00AC_E982_4B5E_ECFD,
+        0x02A6_76CB_F0EE_A1CD,
+        0x0000_0001_6600_7C08,
+    ],
+    [
+        0x00EE_415A_1581_2ED9,
+        0x003D_6C02_0077_B918,
+        0x00FD_2063_5713_2B92,
+        0x017B_E87D_3F5F_FACD,
+        0x02BB_A6FF_6EE5_A437,
+        0x038F_A9FA_80EF_377E,
+        0x0000_0001_6A3E_F08B,
+    ],
+    [
+        0x0323_3D9D_5553_5D4A,
+        0x03F8_BDEE_E492_20DA,
+        0x0350_C4BF_39B4_852C,
+        0x0393_1ABD_6482_AF15,
+        0x03D1_D74C_C4F9_FB0C,
+        0x00DB_1848_C686_F953,
+        0x0000_0001_866C_8ED3,
+    ],
+    [
+        0x006E_F48B_B891_3F55,
+        0x0217_A8F5_4A6C_D78D,
+        0x0192_E7EA_7D4F_BC73,
+        0x018F_84F6_1EED_4C21,
+        0x03D9_4A84_9032_16F7,
+        0x01C2_9B87_3AA0_8165,
+        0x0000_0001_67A5_5CDA,
+    ],
+    [
+        0x00F8_B49C_BA8F_6AA8,
+        0x0170_A7D3_E0C1_8100,
+        0x01B3_6E63_6A5C_871A,
+        0x00E6_ED86_98A4_3964,
+        0x001A_D291_1D9C_6DD0,
+        0x03A9_016F_523C_0428,
+        0x0000_0000_4D2F_259E,
+    ],
+    [
+        0x0284_B529_E256_1092,
+        0x025A_261B_DFAE_FAA5,
+        0x01A8_8CEA_7913_516F,
+        0x022B_BF39_0B4A_303E,
+        0x0248_C50C_477F_94FF,
+        0x0207_40CF_FD61_4B07,
+        0x0000_0000_ACCB_B674,
+    ],
+    [
+        0x0299_B138_5733_45CC,
+        0x0001_D8F8_EE42_B047,
+        0x02EF_9A00_D9B8_6930,
+        0x0366_2B7C_0899_F573,
+        0x00B4_5F14_9654_3346,
+        0x031D_9FF8_F0D8_4C51,
+        0x0000_0000_AD6B_9514,
+    ],
+    [
+        0x01FA_DC13_26ED_06F7,
+        0x0145_EF61_C533_2034,
+        0x00DF_2794_2480_E420,
+        0x0253_9CA4_9F07_2DD2,
+        0x0153_CD76_F2BF_565B,
+        0x02CB_93CE_D8A2_F743,
+        0x0000_0000_2660_400E,
+    ],
+    [
+        0x0015_473A_1D63_4B8F,
+        0x00BD_5C3C_4D25_E011,
+        0x03CD_6356_CAA2_05CA,
+        0x0197_89CE_E14C_C93B,
+        0x020D_7819_C171_C40F,
+        0x01B7_700F_9AC9_0957,
+        0x0000_0000_E0FA_1D81,
+    ],
+    [
+        0x0000_0000_0000_0001,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+];
+
 #[cfg(feature = "std")]
 lazy_static! {
     pub static ref GENERATORG1: GroupG1 = GroupG1::generator();
     pub static ref GENERATORG2: GroupG2 = GroupG2::generator();
+    pub static ref GENERATORG1_TABLE: Vec<[GroupG1; 1 << G1_FIXED_BASE_WINDOW_BITS]> =
+        g1_fixed_base_table();
+    pub static ref GENERATORG1_NEGATIVE: GroupG1 = {
+        let mut g = GroupG1::generator();
+        g.neg();
+        g
+    };
+}
+
+// Negation of the G1 generator, -G1
+//
+// Verification checks e(S, -G1) * e(H(m), PK) == 1 so this is paired with the signature.
+pub fn generator_g1_negative() -> GroupG1 {
+    #[cfg(feature = "std")]
+    {
+        let mut g = GroupG1::new();
+        g.copy(&GENERATORG1_NEGATIVE);
+        g
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let mut g = GroupG1::generator();
+        g.neg();
+        g
+    }
+}
+
+// Precompute j * 16^i * G1 for each window i and each digit j.
+#[cfg(feature = "std")]
+fn g1_fixed_base_table() -> Vec<[GroupG1; 1 << G1_FIXED_BASE_WINDOW_BITS]> {
+    let mut table = Vec::with_capacity(G1_FIXED_BASE_WINDOWS);
+    let mut base = GroupG1::generator();
+    for _ in 0..G1_FIXED_BASE_WINDOWS {
+        let mut row = [GroupG1::new(); 1 << G1_FIXED_BASE_WINDOW_BITS];
+        for j in 1..row.len() {
+            let mut next = row[j - 1];
+            next.add(&base);
+            next.affine();
+            row[j] = next;
+        }
+        table.push(row);
+        for _ in 0..G1_FIXED_BASE_WINDOW_BITS {
+            base.dbl();
+        }
+    }
+    table
+}
+
+// Multiply the G1 generator by a scalar using the precomputed GENERATORG1_TABLE.
+//
+// The scalar is reduced modulo the curve order, then each 4 bit digit selects one table entry.
+// Every entry in a row is read with cmove so the memory access pattern is independent of the
+// scalar. The result is affine and equal to GENERATORG1.mul(scalar).
+#[cfg(feature = "std")]
+pub fn g1_generator_mul(scalar: &BigNum) -> GroupG1 {
+    let mut x = BigNum::new_copy(scalar);
+    x.rmod(&BigNum::new_ints(&CURVE_ORDER));
+    let mut bytes = [0 as u8; MODBYTES];
+    x.tobytes(&mut bytes);
+
+    let mut result = GroupG1::new();
+    for (i, row) in GENERATORG1_TABLE.iter().enumerate() {
+        // Windows are numbered from the least significant digit
+        let byte = bytes[MODBYTES - 1 - i / 2];
+        let digit = if i % 2 == 0 { byte & 0xf } else { byte >> 4 };
+
+        let mut selected = GroupG1::new();
+        for (j, entry) in row.iter().enumerate() {
+            selected.cmove(entry, ct_eq(j as u8, digit));
+        }
+        result.add(&selected);
+    }
+    result.affine();
+
+    bytes.zeroize();
+    x.w.zeroize();
+    result
+}
+
+// Returns 1 if a == b else 0, without branching.
+#[cfg(feature = "std")]
+fn ct_eq(a: u8, b: u8) -> isize {
+    let x = (a ^ b) as isize;
+    ((x - 1) >> 8) & 1
 }
 
 // Take given message and domain and convert it to GroupG2 point
+//
+// The domain is part of both hash inputs: the real part of x is hashed from
+// `msg || domain || 1` and the imaginary part from `msg || domain || 2`, where
+// domain is encoded as 8 big-endian bytes (as in the phase 0 eth2 scheme).
+//
+// Signing and verification both go through this function so they always agree.
 pub fn hash_on_g2(msg: &[u8], d: u64) -> GroupG2 {
-    // Converting to BigNum requires 48 bytes, Keccak256 is only 32 bytes
-    let mut x_real = vec![0 as u8; 16];
-    x_real.append(&mut hash(&[msg, &d.to_le_bytes(), &[1]].concat()));
-    let mut x_imaginary = vec![0 as u8; 16];
-    x_imaginary.append(&mut hash(&[msg, &d.to_le_bytes(), &[2]].concat()));
+    let (x_real, x_imaginary) = hash_msg_domain(msg, &d.to_be_bytes());
+    map_to_g2(&x_real, &x_imaginary)
+}
 
+// As hash_on_g2 but with the domain as an arbitrary byte string, which is hashed as
+// `msg || domain || 1` and `msg || domain || 2`.
+//
+// A u64 domain d is the same as its 8 big-endian bytes, so this agrees with hash_on_g2 for
+// `&d.to_be_bytes()`. Nothing separates msg from domain in the hash input, so an application
+// should always use domains of the same length.
+pub fn hash_on_g2_with_domain_bytes(msg: &[u8], domain: &[u8]) -> GroupG2 {
+    let (x_real, x_imaginary) = hash_msg_domain(msg, domain);
     map_to_g2(&x_real, &x_imaginary)
 }
 
+// As hash_on_g2 but without multiplying by the cofactor.
+//
+// The result is on the curve but generally not in the G2 subgroup, so signatures made from it
+// are invalid. This is only for research and validating intermediate test vectors.
+pub fn hash_on_g2_no_clear(msg: &[u8], d: u64) -> GroupG2 {
+    let (x_real, x_imaginary) = hash_msg_domain(msg, &d.to_be_bytes());
+    map_to_g2_no_clear(&x_real, &x_imaginary)
+}
+
+// Hash a message to a point in G1 with the given domain separation tag.
+//
+// This is hash_to_curve_g1, the BLS12381G1_XMD:SHA-256_SSWU_RO_ suite from RFC 9380, and is
+// the G1 counterpart of hash_on_g2 for schemes with signatures in G1. The result is in the G1
+// subgroup and affine.
+pub fn hash_on_g1(msg: &[u8], dst: &[u8]) -> GroupG1 {
+    hash_to_curve_g1(msg, dst)
+}
+
+// Hash message and domain to the 48 byte real and imaginary parts of x.
+fn hash_msg_domain(msg: &[u8], domain: &[u8]) -> ([u8; MODBYTES], [u8; MODBYTES]) {
+    let mut x_real = [0; MODBYTES];
+    let mut x_imaginary = [0; MODBYTES];
+    hash_msg_domain_tag(msg, domain, 1, &mut x_real);
+    hash_msg_domain_tag(msg, domain, 2, &mut x_imaginary);
+    (x_real, x_imaginary)
+}
+
+// Hash `msg || domain || tag` into the low bytes of x.
+//
+// The parts are streamed into the hash, so the message is never copied.
+fn hash_msg_domain_tag(msg: &[u8], domain: &[u8], tag: u8, x: &mut [u8]) {
+    let mut context = Context::new(&SHA256);
+    context.update(msg);
+    context.update(domain);
+    context.update(&[tag]);
+
+    // Converting to BigNum requires 48 bytes, SHA256 is only 32 bytes
+    x[MODBYTES - SHA256_BYTES..].copy_from_slice(context.finish().as_ref());
+}
+
 // Convert x real and imaginary parts to GroupG2 point
-#[allow(non_snake_case)]
 pub fn map_to_g2(x_real: &[u8], x_imaginary: &[u8]) -> GroupG2 {
+    let mut curve_point = map_to_g2_no_clear(x_real, x_imaginary);
+
+    // Multiply the point by given G2_Cofactor
+    multiply_cofactor(&mut curve_point)
+}
+
+// Convert x real and imaginary parts to a point on the G2 curve, before the cofactor is cleared.
+#[allow(non_snake_case)]
+pub fn map_to_g2_no_clear(x_real: &[u8], x_imaginary: &[u8]) -> GroupG2 {
     // Convery Hashes to BigNums mod q
     let q = BigNum::new_ints(&rom::MODULUS);
     let mut x_real = BigNum::frombytes(x_real);
@@ -112,26 +1169,43 @@ pub fn map_to_g2(x_real: &[u8], x_imaginary: &[u8]) -> GroupG2 {
     if cmp_fp2(&mut y, &mut neg_y) < 0 {
         curve_point.neg();
     }
-
-    // Multiply the point by given G2_Cofactor
-    multiply_cofactor(&mut curve_point)
+    curve_point
 }
 
 // Compare values of two FP2 elements,
 // -1 if num1 < num2; 0 if num1 == num2; 1 if num1 > num2
+//
+// With the `ct` feature the comparison is constant time, see ct_less_than.
 pub fn cmp_fp2(num1: &mut FP2, num2: &mut FP2) -> isize {
     // First compare FP2.b
     let num1_b = num1.getb();
     let num2_b = num2.getb();
-    let mut result = BigNum::comp(&num1_b, &num2_b);
 
-    // If FP2.b is equal compare FP2.a
-    if result == 0 {
+    #[cfg(feature = "ct")]
+    {
         let num1_a = num1.geta();
         let num2_a = num2.geta();
-        result = BigNum::comp(&num1_a, &num2_a);
+        let cmp_b =
+            ct_less_than(&num2_b, &num1_b) as isize - ct_less_than(&num1_b, &num2_b) as isize;
+        let cmp_a =
+            ct_less_than(&num2_a, &num1_a) as isize - ct_less_than(&num1_a, &num2_a) as isize;
+
+        // cmp_a only counts if FP2.b is equal
+        let b_equal = 1 - (cmp_b & 1);
+        cmp_b + b_equal * cmp_a
+    }
+    #[cfg(not(feature = "ct"))]
+    {
+        let mut result = BigNum::comp(&num1_b, &num2_b);
+
+        // If FP2.b is equal compare FP2.a
+        if result == 0 {
+            let num1_a = num1.geta();
+            let num2_a = num2.geta();
+            result = BigNum::comp(&num1_a, &num2_a);
+        }
+        result
     }
-    result
 }
 
 // Multiply in parts by cofactor due to its size.
@@ -153,11 +1227,412 @@ pub fn multiply_cofactor(curve_point: &mut GroupG2) -> GroupG2 {
     curve_point
 }
 
+// Expand a message to len_in_bytes uniformly random bytes with SHA-256, this is
+// expand_message_xmd from RFC 9380 section 5.3.1.
+//
+// Panics if len_in_bytes is greater than 255 * 32.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    let ell = (len_in_bytes + SHA256_BYTES - 1) / SHA256_BYTES;
+    assert!(ell <= 255, "expand_message_xmd: len_in_bytes is too large");
+
+    // A domain separation tag longer than 255 bytes is hashed first (RFC 9380 section 5.3.3)
+    let dst = if dst.len() > 255 {
+        hash(&[&b"H2C-OVERSIZE-DST-"[..], dst].concat())
+    } else {
+        dst.to_vec()
+    };
+    let dst_prime = [&dst[..], &[dst.len() as u8]].concat();
+
+    let b_0 = hash(
+        &[
+            &[0; SHA256_BLOCK_BYTES][..],
+            msg,
+            &(len_in_bytes as u16).to_be_bytes(),
+            &[0],
+            &dst_prime[..],
+        ]
+        .concat(),
+    );
+    let mut b_i = hash(&[&b_0[..], &[1], &dst_prime[..]].concat());
+    let mut uniform_bytes = b_i.clone();
+    for i in 2..=ell {
+        let b_0_xor_b_i: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = hash(&[&b_0_xor_b_i[..], &[i as u8], &dst_prime[..]].concat());
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+// Hash a message to count FP2 elements, this is hash_to_field from RFC 9380 section 5.2
+// using expand_message_xmd with SHA-256.
+pub fn hash_to_field_fp2(msg: &[u8], dst: &[u8], count: usize) -> Vec<FP2> {
+    let uniform_bytes = expand_message_xmd(msg, dst, count * 2 * HASH_TO_FIELD_BYTES);
+    uniform_bytes
+        .chunks(2 * HASH_TO_FIELD_BYTES)
+        .map(|bytes| {
+            let real = reduce_to_field(&bytes[..HASH_TO_FIELD_BYTES]);
+            let imaginary = reduce_to_field(&bytes[HASH_TO_FIELD_BYTES..]);
+            FP2::new_bigs(&real, &imaginary)
+        })
+        .collect()
+}
+
+// Interpret HASH_TO_FIELD_BYTES big-endian bytes as an integer and reduce it modulo q.
+fn reduce_to_field(bytes: &[u8]) -> BigNum {
+    let q = BigNum::new_ints(&rom::MODULUS);
+
+    // Too large for a BigNum, so split into high and low parts and combine in a DBIG
+    let split = bytes.len() - MODBYTES;
+    let mut high = [0; MODBYTES];
+    high[MODBYTES - split..].copy_from_slice(&bytes[..split]);
+    let mut value = DBIG::new_scopy(&BigNum::frombytes(&high));
+    value.shl(8 * MODBYTES);
+    value.add(&DBIG::new_scopy(&BigNum::frombytes(&bytes[split..])));
+    value.norm();
+    value.dmod(&q)
+}
+
+// Map an FP2 element to a point on the G2 curve, this is map_to_curve from RFC 9380
+// section 6.6.3.
+//
+// The simplified SWU map is applied on the isogenous curve E2': y^2 = x^3 + A' * x + B'
+// followed by the 3-isogeny map to E2. The result is not in the G2 subgroup until the
+// cofactor is cleared.
+pub fn map_to_curve_g2(u: &FP2) -> GroupG2 {
+    let mut u = FP2::new_copy(u);
+    let (x, y) = simplified_swu_g2(&mut u);
+    iso3_map(&x, &y)
+}
+
+// Hash a message to a point in G2, this is hash_to_curve from RFC 9380 section 3 for the
+// BLS12381G2_XMD:SHA-256_SSWU_RO_ suite with the given domain separation tag.
+pub fn hash_to_curve_g2(msg: &[u8], dst: &[u8]) -> GroupG2 {
+    let u = hash_to_field_fp2(msg, dst, 2);
+    let mut point = map_to_curve_g2(&u[0]);
+    point.add(&map_to_curve_g2(&u[1]));
+    let mut point = clear_cofactor_g2(&point);
+    point.affine();
+    point
+}
+
+// Domain separation tag prefix used by domain_to_dst, the BLS_SIG ciphersuite id.
+pub const LEGACY_DOMAIN_DST_PREFIX: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// Map a legacy u64 domain to a domain separation tag for hash_to_curve_g2.
+//
+// The tag is `LEGACY_DOMAIN_DST_PREFIX || domain`, with domain as 8 big-endian bytes (the same
+// encoding hash_on_g2 uses). Distinct domains give distinct tags so domain separation carries
+// over. The two hashes are different functions, so a legacy signature does not verify under
+// the mapped tag; messages must be re-signed when migrating.
+pub fn domain_to_dst(domain: u64) -> Vec<u8> {
+    let mut dst = Vec::with_capacity(LEGACY_DOMAIN_DST_PREFIX.len() + 8);
+    dst.extend_from_slice(LEGACY_DOMAIN_DST_PREFIX);
+    dst.extend_from_slice(&domain.to_be_bytes());
+    dst
+}
+
+// Multiply a point on the G2 curve by h_eff, this is clear_cofactor from RFC 9380 section 7.
+pub fn clear_cofactor_g2(point: &GroupG2) -> GroupG2 {
+    let h_eff_high = BigNum::new_ints(&G2_H_EFF_HIGH);
+    let h_eff_shift = BigNum::new_ints(&G2_H_EFF_SHIFT);
+    let h_eff_low = BigNum::new_ints(&G2_H_EFF_LOW);
+
+    // Multiply high part, then low part, then add together
+    let mut result = point.mul(&h_eff_high).mul(&h_eff_shift);
+    result.add(&point.mul(&h_eff_low));
+    result
+}
+
+// The simplified SWU map from RFC 9380 section 6.6.2 onto E2', returns (x, y).
+fn simplified_swu_g2(u: &mut FP2) -> (FP2, FP2) {
+    let a = FP2::new_ints(0, 240);
+    let b = FP2::new_ints(1012, 1012);
+    let mut z = FP2::new_ints(2, 1);
+    z.neg();
+
+    // tv1 = Z^2 * u^4 + Z * u^2
+    let mut z_u2 = FP2::new_copy(u);
+    z_u2.sqr();
+    z_u2.mul(&z);
+    let mut tv1 = FP2::new_copy(&z_u2);
+    tv1.sqr();
+    tv1.add(&z_u2);
+    tv1.norm();
+
+    // x1 = -B / A * (1 + 1 / tv1), or B / (Z * A) if tv1 is zero
+    let mut x1 = FP2::new_copy(&b);
+    if tv1.iszilch() {
+        let mut z_a = FP2::new_copy(&z);
+        z_a.mul(&a);
+        z_a.inverse();
+        x1.mul(&z_a);
+    } else {
+        let mut one = FP2::new();
+        one.one();
+        tv1.inverse();
+        tv1.add(&one);
+        tv1.norm();
+        let mut a_inv = FP2::new_copy(&a);
+        a_inv.inverse();
+        x1.neg();
+        x1.mul(&a_inv);
+        x1.mul(&tv1);
+    }
+
+    // If g(x1) is not square then g(x2) is, where x2 = Z * u^2 * x1
+    let mut x = x1;
+    let mut y = iso_curve_rhs(&x, &a, &b);
+    if !y.sqrt() {
+        x.mul(&z_u2);
+        y = iso_curve_rhs(&x, &a, &b);
+        y.sqrt();
+    }
+
+    // Fix the sign of y to match u
+    if sgn0_fp2(u) != sgn0_fp2(&mut y) {
+        y.neg();
+    }
+    (x, y)
+}
+
+// Calculates g(x) = x^3 + A * x + B
+fn iso_curve_rhs(x: &FP2, a: &FP2, b: &FP2) -> FP2 {
+    let mut rhs = FP2::new_copy(x);
+    rhs.sqr();
+    rhs.add(a);
+    rhs.norm();
+    rhs.mul(x);
+    rhs.add(b);
+    rhs.norm();
+    rhs
+}
+
+// The 3-isogeny map from E2' to E2, points in the kernel are mapped to infinity.
+fn iso3_map(x: &FP2, y: &FP2) -> GroupG2 {
+    let mut x_den = eval_fp2_poly(&ISO3_XDEN, x);
+    let mut y_den = eval_fp2_poly(&ISO3_YDEN, x);
+    if x_den.iszilch() || y_den.iszilch() {
+        return GroupG2::new();
+    }
+    x_den.inverse();
+    y_den.inverse();
+
+    let mut x_mapped = eval_fp2_poly(&ISO3_XNUM, x);
+    x_mapped.mul(&x_den);
+    let mut y_mapped = eval_fp2_poly(&ISO3_YNUM, x);
+    y_mapped.mul(&y_den);
+    y_mapped.mul(y);
+    GroupG2::new_fp2s(&x_mapped, &y_mapped)
+}
+
+// Evaluate a polynomial with (real, imaginary) coefficients, from the constant term, at x.
+fn eval_fp2_poly(coefficients: &[[[Chunk; NLEN]; 2]], x: &FP2) -> FP2 {
+    let mut result = FP2::new();
+    for coefficient in coefficients.iter().rev() {
+        result.mul(x);
+        result.add(&FP2::new_bigs(
+            &BigNum::new_ints(&coefficient[0]),
+            &BigNum::new_ints(&coefficient[1]),
+        ));
+        result.norm();
+    }
+    result
+}
+
+// The sign of an FP2 element from RFC 9380 section 4.1, this is the parity of the real part
+// or the parity of the imaginary part if the real part is zero.
+fn sgn0_fp2(x: &mut FP2) -> isize {
+    let real = x.geta();
+    let imaginary = x.getb();
+    if real.iszilch() {
+        imaginary.parity()
+    } else {
+        real.parity()
+    }
+}
+
+// Hash a message to count FP elements, this is hash_to_field from RFC 9380 section 5.2
+// using expand_message_xmd with SHA-256.
+pub fn hash_to_field_fp(msg: &[u8], dst: &[u8], count: usize) -> Vec<FP> {
+    let uniform_bytes = expand_message_xmd(msg, dst, count * HASH_TO_FIELD_BYTES);
+    uniform_bytes
+        .chunks(HASH_TO_FIELD_BYTES)
+        .map(|bytes| FP::new_big(&reduce_to_field(bytes)))
+        .collect()
+}
+
+// Map an FP element to a point on the G1 curve, this is map_to_curve from RFC 9380
+// section 6.6.3.
+//
+// The simplified SWU map is applied on the isogenous curve E1': y^2 = x^3 + A' * x + B'
+// followed by the 11-isogeny map to E1. The result is not in the G1 subgroup until the
+// cofactor is cleared.
+pub fn map_to_curve_g1(u: &FP) -> GroupG1 {
+    let mut u = FP::new_copy(u);
+    let (x, y) = simplified_swu_g1(&mut u);
+    iso11_map(&x, &y)
+}
+
+// Hash a message to a point in G1, this is hash_to_curve from RFC 9380 section 3 for the
+// BLS12381G1_XMD:SHA-256_SSWU_RO_ suite with the given domain separation tag.
+pub fn hash_to_curve_g1(msg: &[u8], dst: &[u8]) -> GroupG1 {
+    let u = hash_to_field_fp(msg, dst, 2);
+    let mut point = map_to_curve_g1(&u[0]);
+    point.add(&map_to_curve_g1(&u[1]));
+    let mut point = clear_cofactor_g1(&point);
+    point.affine();
+    point
+}
+
+// Multiply a point on the G1 curve by h_eff, this is clear_cofactor from RFC 9380 section 7.
+pub fn clear_cofactor_g1(point: &GroupG1) -> GroupG1 {
+    point.mul(&BigNum::new_ints(&G1_H_EFF))
+}
+
+// The simplified SWU map from RFC 9380 section 6.6.2 onto E1', returns (x, y).
+fn simplified_swu_g1(u: &mut FP) -> (FP, FP) {
+    let a = FP::new_big(&BigNum::new_ints(&ISO11_A));
+    let b = FP::new_big(&BigNum::new_ints(&ISO11_B));
+    let z = FP::new_int(11);
+
+    // tv1 = Z^2 * u^4 + Z * u^2
+    let mut z_u2 = FP::new_copy(u);
+    z_u2.sqr();
+    z_u2.mul(&z);
+    let mut tv1 = FP::new_copy(&z_u2);
+    tv1.sqr();
+    tv1.add(&z_u2);
+    tv1.norm();
+
+    // x1 = -B / A * (1 + 1 / tv1), or B / (Z * A) if tv1 is zero
+    let mut x1 = FP::new_copy(&b);
+    if tv1.iszilch() {
+        let mut z_a = FP::new_copy(&z);
+        z_a.mul(&a);
+        z_a.inverse();
+        x1.mul(&z_a);
+    } else {
+        tv1.inverse();
+        tv1.add(&FP::new_int(1));
+        tv1.norm();
+        let mut a_inv = FP::new_copy(&a);
+        a_inv.inverse();
+        x1.neg();
+        x1.mul(&a_inv);
+        x1.mul(&tv1);
+    }
+
+    // If g(x1) is not square then g(x2) is, where x2 = Z * u^2 * x1
+    let mut x = x1;
+    let mut y = iso_curve_rhs_fp(&x, &a, &b);
+    if !sqrt_fp(&mut y) {
+        x.mul(&z_u2);
+        y = iso_curve_rhs_fp(&x, &a, &b);
+        sqrt_fp(&mut y);
+    }
+
+    // Fix the sign of y to match u
+    if sgn0_fp(u) != sgn0_fp(&mut y) {
+        y.neg();
+    }
+    (x, y)
+}
+
+// Calculates g(x) = x^3 + A * x + B
+fn iso_curve_rhs_fp(x: &FP, a: &FP, b: &FP) -> FP {
+    let mut rhs = FP::new_copy(x);
+    rhs.sqr();
+    rhs.add(a);
+    rhs.norm();
+    rhs.mul(x);
+    rhs.add(b);
+    rhs.norm();
+    rhs
+}
+
+// Replace x with its square root and return true, or leave x unchanged and return false if x
+// is not a square.
+fn sqrt_fp(x: &mut FP) -> bool {
+    let root = x.sqrt();
+    let mut square = FP::new_copy(&root);
+    square.sqr();
+    if !square.equals(x) {
+        return false;
+    }
+    x.copy(&root);
+    true
+}
+
+// The 11-isogeny map from E1' to E1, points in the kernel are mapped to infinity.
+fn iso11_map(x: &FP, y: &FP) -> GroupG1 {
+    let mut x_den = eval_fp_poly(&ISO11_XDEN, x);
+    let mut y_den = eval_fp_poly(&ISO11_YDEN, x);
+    if x_den.iszilch() || y_den.iszilch() {
+        return GroupG1::new();
+    }
+    x_den.inverse();
+    y_den.inverse();
+
+    let mut x_mapped = eval_fp_poly(&ISO11_XNUM, x);
+    x_mapped.mul(&x_den);
+    let mut y_mapped = eval_fp_poly(&ISO11_YNUM, x);
+    y_mapped.mul(&y_den);
+    y_mapped.mul(y);
+    GroupG1::new_bigs(&x_mapped.redc(), &y_mapped.redc())
+}
+
+// Evaluate a polynomial with coefficients from the constant term at x.
+fn eval_fp_poly(coefficients: &[[Chunk; NLEN]], x: &FP) -> FP {
+    let mut result = FP::new();
+    for coefficient in coefficients.iter().rev() {
+        result.mul(x);
+        result.add(&FP::new_big(&BigNum::new_ints(coefficient)));
+        result.norm();
+    }
+    result
+}
+
+// The sign of an FP element from RFC 9380 section 4.1, this is its parity.
+fn sgn0_fp(x: &mut FP) -> isize {
+    x.redc().parity()
+}
+
 // Provides a Keccak256 hash of given input.
 pub fn hash(input: &[u8]) -> Vec<u8> {
     digest(&SHA256, input).as_ref().into()
 }
 
+// Convert a u64 to a BigNum scalar reduced modulo the curve order.
+pub fn u64_to_scalar(n: u64) -> BigNum {
+    let mut bytes = [0 as u8; MOD_BYTE_SIZE];
+    bytes[MOD_BYTE_SIZE - 8..].copy_from_slice(&n.to_be_bytes());
+    let mut scalar = BigNum::frombytes(&bytes);
+    scalar.rmod(&BigNum::new_ints(&CURVE_ORDER));
+    scalar
+}
+
+// Write bytes as 0x prefixed hex, this works without std.
+pub fn fmt_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+// Check a GroupG1 point is in the subgroup of order r, i.e. r * P is infinity.
+pub fn is_in_g1_subgroup(point: &GroupG1) -> bool {
+    let order = BigNum::new_ints(&CURVE_ORDER);
+    point.mul(&order).is_infinity()
+}
+
+// Check a GroupG2 point is in the subgroup of order r, i.e. r * P is infinity.
+pub fn is_in_g2_subgroup(point: &GroupG2) -> bool {
+    let order = BigNum::new_ints(&CURVE_ORDER);
+    point.mul(&order).is_infinity()
+}
+
 // A pairing function for an GroupG2 point and GroupG1 point to FP12.
 pub fn ate_pairing(point_g2: &GroupG2, point_g1: &GroupG1) -> FP12 {
     let e = ate(&point_g2, &point_g1);
@@ -171,6 +1646,56 @@ pub fn ate2_evaluation(a: &GroupG2, b: &GroupG1, c: &GroupG2, d: &GroupG1) -> bo
     FP12::new_int(1).equals(&mut e)
 }
 
+// Check an FP12 element is in GT, the subgroup of order r, i.e. x^r is one.
+//
+// FP12::pow squares with usqr and inverts with conj, which are only correct for elements already
+// in the cyclotomic subgroup. The input here is arbitrary, so x^r is computed with generic
+// square and multiply instead.
+pub fn is_in_gt_subgroup(x: &FP12) -> bool {
+    let order = BigNum::new_ints(&CURVE_ORDER);
+    let mut result = FP12::new_int(1);
+    let mut started = false;
+    for i in (0..MODBYTES * 8).rev() {
+        if started {
+            result.sqr();
+        }
+        if order.bit(i) == 1 {
+            result.mul(x);
+            started = true;
+        }
+    }
+    result.isunity()
+}
+
+// Take an FP12 element and convert it to a 384*12 bit array.
+//
+// This is the Milagro FP12 encoding, each of the twelve coefficients as 48 big-endian bytes.
+pub fn fp12_to_bytes(x: &mut FP12) -> Vec<u8> {
+    let mut result: Vec<u8> = vec![0; GT_BYTE_SIZE];
+    x.tobytes(&mut result);
+    result
+}
+
+// Take a 384*12 bit array and convert it to an FP12 element in GT.
+pub fn fp12_from_bytes(bytes: &[u8]) -> Result<FP12, DecodeError> {
+    if bytes.len() != GT_BYTE_SIZE {
+        return Err(DecodeError::IncorrectSize);
+    }
+
+    // Each coefficient must be less than q, FP12::frombytes would otherwise reduce it
+    for coefficient in bytes.chunks(MODBYTES) {
+        if !is_canonical(&BigNum::frombytes(coefficient)) {
+            return Err(DecodeError::NonCanonicalEncoding);
+        }
+    }
+
+    let x = FP12::frombytes(bytes);
+    if !is_in_gt_subgroup(&x) {
+        return Err(DecodeError::NotInSubgroup);
+    }
+    Ok(x)
+}
+
 // Take a GroupG1 point (x, y) and compress it to a 384 bit array.
 pub fn compress_g1(g1: &mut GroupG1) -> Vec<u8> {
     // A compressed point takes form (c_flag, b_flag, a_flag, x-coordinate) where:
@@ -187,6 +1712,9 @@ pub fn compress_g1(g1: &mut GroupG1) -> Vec<u8> {
         return result;
     }
 
+    // Normalize to affine so the bytes do not depend on the projective representation
+    g1.affine();
+
     // Convert point to array of bytes (x, y)
     let mut g1_bytes: Vec<u8> = vec![0; G1_BYTE_SIZE + 1];
     g1.tobytes(&mut g1_bytes, false);
@@ -203,43 +1731,37 @@ pub fn compress_g1(g1: &mut GroupG1) -> Vec<u8> {
     result
 }
 
+// Flag bits in the first byte of a compressed point, see check_compressed_flags.
+const C_FLAG: u8 = 0x80;
+const B_FLAG: u8 = 0x40;
+const A_FLAG: u8 = 0x20;
+const FLAG_BITS: u8 = C_FLAG | B_FLAG | A_FLAG;
+
 // Take a 384 bit array and convert to GroupG1 point (x, y)
+//
+// Never panics, any malformed input is a DecodeError.
 pub fn decompress_g1(g1_bytes: &[u8]) -> Result<GroupG1, DecodeError> {
-    // Length must be 48 bytes
-    if g1_bytes.len() != MODBYTES {
-        return Err(DecodeError::IncorrectSize);
-    }
-
-    let a_flag: u8 = g1_bytes[0] % u8::pow(2, 6) / u8::pow(2, 5);
-
-    // c_flag must be set
-    if g1_bytes[0] / u8::pow(2, 7) != 1 {
-        // Invalid bytes
-        return Err(DecodeError::InvalidCFlag);
-    }
-
-    // Check b_flag
-    if g1_bytes[0] % u8::pow(2, 7) / u8::pow(2, 6) == 1 {
-        // If b_flag == 1 -> a_flag == x == 0
-        if a_flag != 0 || g1_bytes[0] % u8::pow(2, 5) != 0 {
-            return Err(DecodeError::BadPoint);
-        }
-
-        for item in g1_bytes.iter().skip(1) {
-            if *item != 0 {
-                return Err(DecodeError::BadPoint);
-            }
-        }
+    // Length must be 48 bytes and only valid flag combinations may be set
+    check_compressed_flags(g1_bytes, MODBYTES)?;
 
+    // Check b_flag, the flags and x are already known to be zero
+    if g1_bytes[0] & B_FLAG != 0 {
         // Point is infinity
         return Ok(GroupG1::new());
     }
 
-    let mut g1_bytes = g1_bytes.to_owned();
+    let a_flag = (g1_bytes[0] & A_FLAG != 0) as u8;
+
+    // Zero the flags so it can be converted to 381 bit BigNum
+    let mut x_bytes = [0 as u8; MODBYTES];
+    x_bytes.copy_from_slice(g1_bytes);
+    x_bytes[0] &= !FLAG_BITS;
+    let x_big = BigNum::frombytes(&x_bytes);
 
-    // Zero remaining flags so it can be converted to 381 bit BigNum
-    g1_bytes[0] %= u8::pow(2, 5);
-    let x_big = BigNum::frombytes(&g1_bytes);
+    // x must be canonical (less than the modulus), otherwise x and x + q would both decode
+    if !is_canonical(&x_big) {
+        return Err(DecodeError::NonCanonicalEncoding);
+    }
 
     // Convert to GroupG1 point using big
     let mut point = GroupG1::new_big(&x_big);
@@ -262,7 +1784,7 @@ pub fn compress_g2(g2: &mut GroupG2) -> Vec<u8> {
     // (c_flag1, b_flag1, a_flag1, x-coordinate.a, 0, 0, 0, x-coordinate.b) where:
     // c_flag1 == 1
     // b_flag1 represents infinity (1 if infinitity -> x = y = 0)
-    // a_flag1 = y_imaginary % 2 (i.e. point.gety().getb())
+    // a_flag1 = sign of y_imaginary, or of y_real if y_imaginary == 0 (see calc_a_flag_fp2)
     // x is the x-coordinate of
 
     // Check point at inifinity
@@ -273,6 +1795,9 @@ pub fn compress_g2(g2: &mut GroupG2) -> Vec<u8> {
         return result;
     }
 
+    // Normalize to affine so the bytes do not depend on the projective representation
+    g2.affine();
+
     // Convert point to array of bytes (x, y)
     let mut g2_bytes: Vec<u8> = vec![0; G2_BYTE_SIZE];
     g2.tobytes(&mut g2_bytes);
@@ -286,7 +1811,10 @@ pub fn compress_g2(g2: &mut GroupG2) -> Vec<u8> {
     result.extend_from_slice(x_real);
 
     // Set flags
-    let a_flag = calc_a_flag(&BigNum::frombytes(&g2_bytes[MODBYTES * 3..]));
+    let a_flag = calc_a_flag_fp2(
+        &BigNum::frombytes(&g2_bytes[(MODBYTES * 2)..(MODBYTES * 3)]),
+        &BigNum::frombytes(&g2_bytes[MODBYTES * 3..]),
+    );
     result[0] += u8::pow(2, 5) * a_flag;
     result[0] += u8::pow(2, 7); // c_flag
 
@@ -294,44 +1822,33 @@ pub fn compress_g2(g2: &mut GroupG2) -> Vec<u8> {
 }
 
 // Take a 384*2 bit array and convert to GroupG2 point (x, y)
+//
+// Never panics, any malformed input is a DecodeError.
 pub fn decompress_g2(g2_bytes: &[u8]) -> Result<GroupG2, DecodeError> {
-    // Length must be 96 bytes
-    if g2_bytes.len() != G2_BYTE_SIZE / 2 {
-        return Err(DecodeError::IncorrectSize);
-    }
-
-    // c_flag must be set
-    if g2_bytes[0] / u8::pow(2, 7) != 1 {
-        // Invalid bytes
-        return Err(DecodeError::InvalidCFlag);
-    }
-
-    // Check b_flag
-    if g2_bytes[0] % u8::pow(2, 7) / u8::pow(2, 6) == 1 {
-        // If b_flag == 1 -> a_flag == x == 0
-        if g2_bytes[0] % u8::pow(2, 6) != 0 {
-            return Err(DecodeError::BadPoint);
-        }
+    // Length must be 96 bytes and only valid flag combinations may be set
+    check_compressed_flags(g2_bytes, G2_BYTE_SIZE / 2)?;
 
-        for item in g2_bytes.iter().skip(1) {
-            if *item != 0 {
-                return Err(DecodeError::BadPoint);
-            }
-        }
+    // Check b_flag, the flags and x are already known to be zero
+    if g2_bytes[0] & B_FLAG != 0 {
         // Point is infinity
         return Ok(GroupG2::new());
     }
 
-    let a_flag: u8 = g2_bytes[0] % u8::pow(2, 6) / u8::pow(2, 5);
-
-    let mut g2_bytes = g2_bytes.to_owned();
+    let a_flag = (g2_bytes[0] & A_FLAG != 0) as u8;
 
-    // Zero remaining flags so it can be converted to 381 bit BigNum
-    g2_bytes[0] %= u8::pow(2, 5);
+    // Zero the flags so it can be converted to 381 bit BigNum
+    let mut x_bytes = [0 as u8; MODBYTES];
+    x_bytes.copy_from_slice(&g2_bytes[..MODBYTES]);
+    x_bytes[0] &= !FLAG_BITS;
 
     // Convert from array to FP2
-    let x_imaginary = BigNum::frombytes(&g2_bytes[0..MODBYTES]);
+    let x_imaginary = BigNum::frombytes(&x_bytes);
     let x_real = BigNum::frombytes(&g2_bytes[MODBYTES..]);
+
+    // Both parts of x must be canonical (less than the modulus)
+    if !is_canonical(&x_imaginary) || !is_canonical(&x_real) {
+        return Err(DecodeError::NonCanonicalEncoding);
+    }
     let x = FP2::new_bigs(&x_real, &x_imaginary);
 
     // Convert to GroupG1 point using big and sign
@@ -341,7 +1858,7 @@ pub fn decompress_g2(g2_bytes: &[u8]) -> Result<GroupG2, DecodeError> {
     }
 
     // Confirm a_flag matches given flag
-    let calculated_a_flag = calc_a_flag(&point.gety().getb());
+    let calculated_a_flag = calc_a_flag_fp2(&point.gety().geta(), &point.gety().getb());
     if calculated_a_flag != a_flag {
         point.neg();
     }
@@ -349,26 +1866,143 @@ pub fn decompress_g2(g2_bytes: &[u8]) -> Result<GroupG2, DecodeError> {
     Ok(point)
 }
 
+// Check the length and flags of a compressed point without decoding it.
+//
+// The x-coordinate is not checked so a true result does not mean the bytes decode.
+pub fn has_valid_compressed_flags(bytes: &[u8], size: usize) -> bool {
+    check_compressed_flags(bytes, size).is_ok()
+}
+
+// Check the length and flag bits of a compressed point of size bytes.
+//
+// Only the c_flag, b_flag and a_flag in the top three bits of the first byte are defined:
+// - the c_flag must be set, otherwise InvalidCFlag
+// - if the b_flag (infinity) is set, the a_flag and every bit of x must be zero
+// - in G2 the top three bits of the second component of x are reserved and must be zero
+// Any other combination is BadPoint.
+//
+// Every index below is guarded by the length check, so this never panics.
+fn check_compressed_flags(bytes: &[u8], size: usize) -> Result<(), DecodeError> {
+    if bytes.len() != size || bytes.is_empty() {
+        return Err(DecodeError::IncorrectSize);
+    }
+
+    // c_flag must be set
+    if bytes[0] & C_FLAG == 0 {
+        return Err(DecodeError::InvalidCFlag);
+    }
+
+    // If b_flag == 1 -> a_flag == x == 0
+    if bytes[0] & B_FLAG != 0
+        && (bytes[0] & !(C_FLAG | B_FLAG) != 0 || bytes[1..].iter().any(|byte| *byte != 0))
+    {
+        return Err(DecodeError::BadPoint);
+    }
+
+    // Reserved bits of the second component in G2
+    if size > MODBYTES && bytes[MODBYTES] & FLAG_BITS != 0 {
+        return Err(DecodeError::BadPoint);
+    }
+    Ok(())
+}
+
+// Check a field element is in canonical form, i.e. less than the modulus q.
+fn is_canonical(x: &BigNum) -> bool {
+    let q = BigNum::new_ints(&rom::MODULUS);
+    BigNum::comp(x, &q) < 0
+}
+
 // Takes a y-value and calculates if a_flag is 1 or 0
 //
 // a_flag = floor((y * 2)  / q)
+//
+// With the `ct` feature this is constant time, see ct_less_than.
 pub fn calc_a_flag(y: &BigNum) -> u8 {
     let mut y2 = *y;
     y2.imul(2);
     let q = BigNum::new_ints(&rom::MODULUS);
 
     // if y * 2 < q => floor(y * 2 / q) = 0
-    if BigNum::comp(&y2, &q) < 0 {
-        return 0;
+    #[cfg(feature = "ct")]
+    {
+        1 - ct_less_than(&y2, &q)
+    }
+    #[cfg(not(feature = "ct"))]
+    {
+        if BigNum::comp(&y2, &q) < 0 {
+            return 0;
+        }
+
+        1
+    }
+}
+
+// Takes the real and imaginary parts of a y-value in FP2 and calculates if a_flag is 1 or 0
+//
+// The flag is taken from the imaginary part unless it is zero, in which case the real part is
+// used. Using only the imaginary part would give y and -y the same flag when it is zero.
+//
+// Note: x = 0 needs no special handling. In G1 the points (0, 2) and (0, -2) already have
+// distinct flags (and are not in the subgroup), in G2 4(1 + i) is not a square so no point
+// has x = 0.
+pub fn calc_a_flag_fp2(y_real: &BigNum, y_imaginary: &BigNum) -> u8 {
+    #[cfg(feature = "ct")]
+    {
+        // Both flags are calculated and one selected so the work does not depend on y
+        let imaginary_zero = ct_is_zero(y_imaginary);
+        imaginary_zero * calc_a_flag(y_real) + (1 - imaginary_zero) * calc_a_flag(y_imaginary)
+    }
+    #[cfg(not(feature = "ct"))]
+    {
+        if y_imaginary.iszilch() {
+            return calc_a_flag(y_real);
+        }
+
+        calc_a_flag(y_imaginary)
+    }
+}
+
+// Returns 1 if a < b else 0, in constant time.
+//
+// BigNum::comp and iszilch return as soon as a limb differs, so their timing depends on the
+// values. That is harmless for public points, which is everything this crate compresses
+// normally, but would leak information about a secret derived point (e.g. an unblinded share).
+// The `ct` feature uses these comparisons for the a_flag of compressed points instead.
+#[cfg(feature = "ct")]
+fn ct_less_than(a: &BigNum, b: &BigNum) -> u8 {
+    // tobytes normalises the limbs so takes &mut self
+    let mut a = BigNum::new_copy(a);
+    let mut b = BigNum::new_copy(b);
+    let mut a_bytes = [0; MODBYTES];
+    let mut b_bytes = [0; MODBYTES];
+    a.tobytes(&mut a_bytes);
+    b.tobytes(&mut b_bytes);
+
+    // Borrow out of a - b, from the least significant byte
+    let mut borrow: i16 = 0;
+    for (a_byte, b_byte) in a_bytes.iter().zip(b_bytes.iter()).rev() {
+        let diff = *a_byte as i16 - *b_byte as i16 - borrow;
+        borrow = (diff >> 8) & 1;
     }
+    borrow as u8
+}
 
-    1
+// Returns 1 if x is zero else 0, in constant time.
+#[cfg(feature = "ct")]
+fn ct_is_zero(x: &BigNum) -> u8 {
+    let mut x = BigNum::new_copy(x);
+    let mut bytes = [0; MODBYTES];
+    x.tobytes(&mut bytes);
+    let any = bytes.iter().fold(0, |acc, byte| acc | *byte) as i16;
+    ((any - 1) >> 8) as u8 & 1
 }
 
 #[cfg(test)]
 mod tests {
+    extern crate rand;
     extern crate yaml_rust;
 
+    use self::rand::Rng;
     use self::yaml_rust::yaml;
     use super::*;
     use std::{fs::File, io::prelude::*, path::PathBuf};
@@ -394,6 +2028,174 @@ mod tests {
         assert_eq!(compressed, compressed_result);
     }
 
+    #[test]
+    fn test_decompress_non_canonical() {
+        // x = q + 4 would otherwise decode to the same point as x = 4
+        let bytes = hex::decode("9a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaaf").unwrap();
+        assert_eq!(
+            decompress_g1(&bytes).err(),
+            Some(DecodeError::NonCanonicalEncoding)
+        );
+
+        // x = (q + 2, 0)
+        let bytes = hex::decode("a000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaad").unwrap();
+        assert_eq!(
+            decompress_g2(&bytes).err(),
+            Some(DecodeError::NonCanonicalEncoding)
+        );
+
+        // x = (2, q)
+        let bytes = hex::decode("ba0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002").unwrap();
+        assert_eq!(
+            decompress_g2(&bytes).err(),
+            Some(DecodeError::NonCanonicalEncoding)
+        );
+    }
+
+    #[test]
+    fn test_decompress_illegal_flags() {
+        let mut g1 = compress_g1(&mut GroupG1::generator());
+        let mut g2 = compress_g2(&mut GroupG2::generator());
+        let infinity_g1 = compress_g1(&mut GroupG1::new());
+        let infinity_g2 = compress_g2(&mut GroupG2::new());
+
+        // c_flag unset with every combination of b_flag and a_flag
+        for flags in &[0x00, 0x20, 0x40, 0x60] {
+            let mut bytes = g1.clone();
+            bytes[0] = bytes[0] % 0x20 + flags;
+            assert_eq!(decompress_g1(&bytes).err(), Some(DecodeError::InvalidCFlag));
+            let mut bytes = g2.clone();
+            bytes[0] = bytes[0] % 0x20 + flags;
+            assert_eq!(decompress_g2(&bytes).err(), Some(DecodeError::InvalidCFlag));
+        }
+
+        // b_flag with a_flag
+        let mut bytes = infinity_g1.clone();
+        bytes[0] |= 0x20;
+        assert_eq!(decompress_g1(&bytes).err(), Some(DecodeError::BadPoint));
+        let mut bytes = infinity_g2.clone();
+        bytes[0] |= 0x20;
+        assert_eq!(decompress_g2(&bytes).err(), Some(DecodeError::BadPoint));
+
+        // b_flag with x set in the first byte or later
+        for i in &[0, 1, 47] {
+            let mut bytes = infinity_g1.clone();
+            bytes[*i] |= 1;
+            assert_eq!(decompress_g1(&bytes).err(), Some(DecodeError::BadPoint));
+        }
+        for i in &[0, 1, 48, 95] {
+            let mut bytes = infinity_g2.clone();
+            bytes[*i] |= 1;
+            assert_eq!(decompress_g2(&bytes).err(), Some(DecodeError::BadPoint));
+        }
+
+        // b_flag on a non-infinity x
+        g1[0] |= 0x40;
+        assert_eq!(decompress_g1(&g1).err(), Some(DecodeError::BadPoint));
+        g2[0] |= 0x40;
+        assert_eq!(decompress_g2(&g2).err(), Some(DecodeError::BadPoint));
+
+        // Flag bits in the second component of a G2 point are reserved
+        for flag in &[0x20, 0x40, 0x80] {
+            let mut bytes = compress_g2(&mut GroupG2::generator());
+            bytes[48] |= flag;
+            assert_eq!(decompress_g2(&bytes).err(), Some(DecodeError::BadPoint));
+        }
+    }
+
+    #[test]
+    fn test_decompress_never_panics() {
+        let mut rng = rand::thread_rng();
+
+        // Every length, including empty input
+        for len in 0..200 {
+            let mut bytes = vec![0 as u8; len];
+            rng.fill(&mut bytes[..]);
+            let _ = decompress_g1(&bytes);
+            let _ = decompress_g2(&bytes);
+            assert!(!has_valid_compressed_flags(&bytes, 0));
+        }
+
+        // Random x with every flag combination, and x all ones or all zeros
+        for _ in 0..50 {
+            let mut g1 = [0 as u8; MODBYTES];
+            let mut g2 = [0 as u8; 2 * MODBYTES];
+            rng.fill(&mut g1[..]);
+            rng.fill(&mut g2[..]);
+            for fill in &[None, Some(0x00), Some(0xff)] {
+                if let Some(byte) = fill {
+                    g1 = [*byte; MODBYTES];
+                    g2 = [*byte; 2 * MODBYTES];
+                }
+                for flags in 0..8 as u8 {
+                    g1[0] = g1[0] & !FLAG_BITS | flags << 5;
+                    g2[0] = g2[0] & !FLAG_BITS | flags << 5;
+                    for reserved in 0..8 as u8 {
+                        g2[MODBYTES] = g2[MODBYTES] & !FLAG_BITS | reserved << 5;
+                        let _ = decompress_g2(&g2);
+                    }
+                    let _ = decompress_g1(&g1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compression_g2_zero_y_imaginary() {
+        // Points on the curve where y = (a, 0) for both signs of a.
+        // These are distinct points so must have distinct encodings which round trip.
+        let compressed_pos = hex::decode("a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000013012ee46c892815c3ee133c0eb6ce1708f7aced12c82cb0a7404ad8ce28e77111a8fe9d10df4f22446c901e8f26165e6a").unwrap();
+        let compressed_neg = hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000013012ee46c892815c3ee133c0eb6ce1708f7aced12c82cb0a7404ad8ce28e77111a8fe9d10df4f22446c901e8f26165e6a").unwrap();
+
+        let mut point_pos = decompress_g2(&compressed_pos).unwrap();
+        let mut point_neg = decompress_g2(&compressed_neg).unwrap();
+        assert!(!point_pos.equals(&mut point_neg));
+        point_neg.neg();
+        assert!(point_pos.equals(&mut point_neg));
+        point_neg.neg();
+
+        assert_eq!(compress_g2(&mut point_pos), compressed_pos);
+        assert_eq!(compress_g2(&mut point_neg), compressed_neg);
+    }
+
+    #[cfg(feature = "ct")]
+    #[test]
+    fn test_ct_comparisons() {
+        let q = BigNum::new_ints(&rom::MODULUS);
+        let mut q_minus_one = q;
+        q_minus_one.dec(1);
+        q_minus_one.norm();
+        let values = [BigNum::new(), BigNum::new_int(1), q_minus_one, q];
+        for a in values.iter() {
+            for b in values.iter() {
+                let expected = (BigNum::comp(a, b) < 0) as u8;
+                assert_eq!(ct_less_than(a, b), expected);
+            }
+            assert_eq!(ct_is_zero(a), a.iszilch() as u8);
+        }
+
+        // The flags match the branching calculation
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let mut point =
+                GroupG2::generator().mul(&BigNum::new_int(rng.gen::<u16>() as isize + 1));
+            point.affine();
+            let mut y = point.gety();
+            let (y_real, y_imaginary) = (y.geta(), y.getb());
+            let mut y2 = y_imaginary;
+            y2.imul(2);
+            let expected = (BigNum::comp(&y2, &q) >= 0) as u8;
+            assert_eq!(calc_a_flag(&y_imaginary), expected);
+            assert_eq!(calc_a_flag_fp2(&y_real, &y_imaginary), expected);
+
+            let mut neg_y = FP2::new_copy(&y);
+            neg_y.neg();
+            neg_y.reduce();
+            assert_eq!(cmp_fp2(&mut y, &mut neg_y), -cmp_fp2(&mut neg_y, &mut y));
+            assert_eq!(cmp_fp2(&mut y, &mut FP2::new_copy(&y)), 0);
+        }
+    }
+
     #[test]
     fn test_to_from_infinity_g1() {
         let mut point = GroupG1::new();
@@ -410,6 +2212,313 @@ mod tests {
         assert_eq!(point.tostring(), round_trip_point.tostring());
     }
 
+    #[test]
+    fn test_hash_on_g2_known_output() {
+        // Pins the hash for a fixed (msg, domain) so any change to how the domain is
+        // folded into the hash is caught.
+        let msg = [0 as u8; 32];
+        let domain = 1;
+        let expected = hex::decode("adb62ce0f23bee07ee9733a4b0c9ac199095083f214f28532f7e56529f6bf5a917a90a4c9eed2a9c5d0922a6c9c3937909add81306482271adcb46f04e27aa7490d3e9aeb81c815fb2950ca61da46d1daafc14cfc838eb15c87b908c18e9f1e3").unwrap();
+
+        let mut point = hash_on_g2(&msg, domain);
+        assert_eq!(compress_g2(&mut point), expected);
+
+        // A different domain must give a different point
+        let mut point = hash_on_g2(&msg, domain + 1);
+        assert_ne!(compress_g2(&mut point), expected);
+    }
+
+    #[test]
+    fn test_hash_on_g2_with_domain_bytes() {
+        let msg = [0 as u8; 32];
+        let mut point = hash_on_g2_with_domain_bytes(&msg, &(1 as u64).to_be_bytes());
+        assert!(point.equals(&mut hash_on_g2(&msg, 1)));
+
+        let mut point = hash_on_g2_with_domain_bytes(&msg, b"some application domain");
+        assert!(!point.equals(&mut hash_on_g2(&msg, 1)));
+        assert!(is_in_g2_subgroup(&point));
+    }
+
+    #[test]
+    fn test_hash_on_g2_no_clear() {
+        let msg = [0 as u8; 32];
+        let domain = 1;
+        let expected = hex::decode("a0000000000000000000000000000000fdbb48328410072d120fe62c2a1be5931c4142d5abf04a3c9f68ba8a36d45ba400000000000000000000000000000000293815c24025e7cc38b4e00cd8e915cc539ab4c892f74e50eae6b21543cbbd8f").unwrap();
+
+        let mut point = hash_on_g2_no_clear(&msg, domain);
+        assert_eq!(compress_g2(&mut point), expected);
+        assert!(!is_in_g2_subgroup(&point));
+
+        // Clearing the cofactor gives hash_on_g2
+        let mut cleared = multiply_cofactor(&mut point);
+        assert!(cleared.equals(&mut hash_on_g2(&msg, domain)));
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // Test vectors from RFC 9380 appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+        let expected =
+            hex::decode("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+                .unwrap();
+        assert_eq!(expand_message_xmd(b"", dst, 0x20), expected);
+
+        let expected =
+            hex::decode("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+                .unwrap();
+        assert_eq!(expand_message_xmd(b"abc", dst, 0x20), expected);
+
+        let expected = hex::decode("af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced").unwrap();
+        assert_eq!(expand_message_xmd(b"", dst, 0x80), expected);
+    }
+
+    #[test]
+    fn test_oversize_dst() {
+        // A 300 byte DST is replaced by SHA256("H2C-OVERSIZE-DST-" || DST), expected values are
+        // from a reference implementation given the reduced DST directly.
+        let dst: Vec<u8> = (0..300).map(|i| b'a' + (i % 26) as u8).collect();
+        let reduced_dst = hash(&[&b"H2C-OVERSIZE-DST-"[..], &dst].concat());
+        assert_eq!(reduced_dst.len(), 32);
+
+        let expected =
+            hex::decode("63197d08d1dfb226a44c6f4c1086a4b4eb570f2c37fac24a4e04adfb07526544")
+                .unwrap();
+        assert_eq!(expand_message_xmd(b"abc", &dst, 0x20), expected);
+        assert_eq!(expand_message_xmd(b"abc", &reduced_dst, 0x20), expected);
+
+        let expected = hex::decode("9201ad28a5a2f42e690b0cf6a744976c36acdad2e845a217e57dd213c355315cd2ee0dd7157147495ecdc236d053e655031bfdf362ce4c4160d5a1bdbc9c5489f792d96207d07f69921a3ed19e5032eb166e99b0510eb5af26e51499f942510b").unwrap();
+        let mut point = hash_to_curve_g2(b"abc", &dst);
+        assert_eq!(compress_g2(&mut point), expected);
+
+        // Not truncated to the first 255 bytes
+        let mut truncated = hash_to_curve_g2(b"abc", &dst[..255]);
+        assert_ne!(compress_g2(&mut truncated), expected);
+    }
+
+    #[test]
+    fn test_hash_to_field_and_map_to_curve_g2() {
+        // Intermediate values of the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite for msg = "abc"
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let expected = [
+            (
+                "15f7c0aa8f6b296ab5ff9c2c7581ade64f4ee6f1bf18f55179ff44a2cf355fa53dd2a2158c5ecb17d7c52f63e7195771",
+                "01c8067bf4c0ba709aa8b9abc3d1cef589a4758e09ef53732d670fd8739a7274e111ba2fcaa71b3d33df2a3a0c8529dd",
+                "85d8a724db78e570e34100c0bc4a5fa84ad5839359b40398151f37cff5a51de945c563463c9efbdda569850ee5a53e7712b2e525281b5f4d2276954e84ac4f42cf4e13b6ac4228624e17760faf94ce5706d53f0ca1952f1c5ef75239aeed55ad",
+            ),
+            (
+                "187111d5e088b6b9acfdfad078c4dacf72dcd17ca17c82be35e79f8c372a693f60a033b461d81b025864a0ad051a06e4",
+                "08b852331c96ed983e497ebc6dee9b75e373d923b729194af8e72a051ea586f3538a6ebb1e80881a082fa2b24df9f566",
+                "b5b0dadc256a258b4c68ea43605dffa6d312eef215c19e6474b3e101d33b661dfee43b51abbf96fee68fc6043ac56a5819f18cc5ec0c2f055e47c802acc3b0e40c337256a208001dde14b25afced146f37ea3d3ce16834c78175b3ed61f3c537",
+            ),
+        ];
+
+        let mut u = hash_to_field_fp2(b"abc", dst, 2);
+        assert_eq!(u.len(), 2);
+        for (u, (real, imaginary, q)) in u.iter_mut().zip(expected.iter()) {
+            let mut bytes = [0 as u8; MODBYTES];
+            u.geta().tobytes(&mut bytes);
+            assert_eq!(bytes.to_vec(), hex::decode(real).unwrap());
+            u.getb().tobytes(&mut bytes);
+            assert_eq!(bytes.to_vec(), hex::decode(imaginary).unwrap());
+
+            let mut point = map_to_curve_g2(u);
+            assert_eq!(compress_g2(&mut point), hex::decode(q).unwrap());
+        }
+
+        // u = 0 takes the exceptional case in the SWU map
+        let mut point = map_to_curve_g2(&FP2::new());
+        assert_eq!(compress_g2(&mut point), hex::decode("8869822666fe850cb93dfd4fa64ebd9ef77ba62b5c12055eadb6e7cc8972f64e01c4577d3d52456c26867647f53665190cdfcc9523305c43ef59a4e347cb3fc76688c60b05bafebd445a65901b5dd40644e21d35dcbe50a95955e4f8e24fbe6f").unwrap());
+    }
+
+    #[test]
+    fn test_hash_to_curve_g2() {
+        // Test vector from RFC 9380 appendix J.10.1 for msg = ""
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let expected = hex::decode("a5cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a").unwrap();
+
+        let mut point = hash_to_curve_g2(b"", dst);
+        assert!(is_in_g2_subgroup(&point));
+        assert_eq!(compress_g2(&mut point), expected);
+    }
+
+    #[test]
+    fn test_hash_to_field_and_map_to_curve_g1() {
+        // Intermediate values of the BLS12381G1_XMD:SHA-256_SSWU_RO_ suite for msg = "abc"
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let expected = [
+            (
+                "0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951",
+                "b25435adce8e1cbd1c803e7123f45392dc6e326d292499c2c45c5865985fd74fe8f042ecdeeec5ecac80680d04317d80",
+            ),
+            (
+                "003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139",
+                "91def93719829ecda3b46aa8c31fc3ac9c34b428982b898369608e4f042babee6c77ab9218aad5c87ba785481eff8ae4",
+            ),
+        ];
+
+        let mut u = hash_to_field_fp(b"abc", dst, 2);
+        assert_eq!(u.len(), 2);
+        for (u, (value, q)) in u.iter_mut().zip(expected.iter()) {
+            let mut bytes = [0 as u8; MODBYTES];
+            u.redc().tobytes(&mut bytes);
+            assert_eq!(bytes.to_vec(), hex::decode(value).unwrap());
+
+            let mut point = map_to_curve_g1(u);
+            assert_eq!(compress_g1(&mut point), hex::decode(q).unwrap());
+        }
+
+        // u = 0 takes the exceptional case in the SWU map
+        let mut point = map_to_curve_g1(&FP::new());
+        assert_eq!(compress_g1(&mut point), hex::decode("9956714e4244749bcdcef542ac99a287d43cb887988b8adabe76cc7d0153351193ea5769ba338d1ac61609ac3d3c8eaf").unwrap());
+    }
+
+    #[test]
+    fn test_hash_on_g1() {
+        // Test vectors from RFC 9380 appendix J.9.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let vectors: [(&[u8], &str); 2] = [
+            (b"", "852926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"),
+            (b"abc", "83567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+        ];
+
+        for (msg, expected) in vectors.iter() {
+            let mut point = hash_on_g1(msg, dst);
+            assert!(is_in_g1_subgroup(&point));
+            assert_eq!(compress_g1(&mut point), hex::decode(expected).unwrap());
+        }
+
+        // Before the cofactor is cleared the mapped point is generally outside the subgroup
+        let u = hash_to_field_fp(b"abc", dst, 1);
+        let point = map_to_curve_g1(&u[0]);
+        assert!(!is_in_g1_subgroup(&point));
+        assert!(is_in_g1_subgroup(&clear_cofactor_g1(&point)));
+    }
+
+    #[test]
+    fn test_g1_generator_mul() {
+        let mut rng = rand::thread_rng();
+        let order = BigNum::new_ints(&CURVE_ORDER);
+        let mut r_minus_one = BigNum::new_copy(&order);
+        r_minus_one.dec(1);
+        r_minus_one.norm();
+        let mut scalars = vec![
+            BigNum::new(),
+            BigNum::new_int(1),
+            BigNum::new_int(15),
+            r_minus_one,
+        ];
+        for _ in 0..5 {
+            let mut bytes = [0 as u8; MODBYTES];
+            rng.fill(&mut bytes[MODBYTES - 32..]);
+            scalars.push(BigNum::frombytes(&bytes));
+        }
+
+        for scalar in &scalars {
+            let mut expected = GroupG1::generator().mul(scalar);
+            let mut result = g1_generator_mul(scalar);
+            assert_eq!(compress_g1(&mut result), compress_g1(&mut expected));
+        }
+    }
+
+    #[test]
+    fn test_domain_to_dst() {
+        let mut expected = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(domain_to_dst(42), expected);
+
+        // Domain separation is preserved through the mapping
+        let msg = b"cats";
+        let mut a = hash_to_curve_g2(msg, &domain_to_dst(42));
+        let mut b = hash_to_curve_g2(msg, &domain_to_dst(43));
+        assert!(!a.equals(&mut b));
+
+        // The mapping does not reproduce the legacy hash, old signatures must be re-signed
+        let mut legacy = hash_on_g2(msg, 42);
+        legacy.affine();
+        assert!(!a.equals(&mut legacy));
+    }
+
+    #[test]
+    fn test_hash_on_g2_cats() {
+        let expected = hex::decode("a5f5adb2ecf73040458ea113e5490bb15e476e0f3b19e82dd80503e5c4bc6f3cffbf16640feb99e5c6b03625718e7fa0060502cd1ac7be59b353a5c6e4c0058e8e829152623e2b14d90db225c271e55fd371f6e2bfe3ede820cad19c1fc3f906").unwrap();
+        let mut point = hash_on_g2("cats".as_bytes(), 42);
+        assert_eq!(compress_g2(&mut point), expected);
+    }
+
+    #[test]
+    fn test_subgroup_checks() {
+        assert!(is_in_g1_subgroup(&GroupG1::generator()));
+        assert!(is_in_g1_subgroup(&GroupG1::new()));
+        assert!(is_in_g2_subgroup(&GroupG2::generator()));
+        assert!(is_in_g2_subgroup(&GroupG2::new()));
+
+        // (4, y) is on the G1 curve but not in the subgroup
+        let bytes = hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap();
+        assert!(!is_in_g1_subgroup(&decompress_g1(&bytes).unwrap()));
+
+        // ((2, 0), y) is on the G2 curve but not in the subgroup
+        let bytes = hex::decode("a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002").unwrap();
+        assert!(!is_in_g2_subgroup(&decompress_g2(&bytes).unwrap()));
+    }
+
+    #[test]
+    fn test_fp12_round_trip() {
+        let mut e = ate_pairing(&GroupG2::generator(), &GroupG1::generator());
+        let bytes = fp12_to_bytes(&mut e);
+        assert_eq!(bytes.len(), GT_BYTE_SIZE);
+
+        let mut decoded = fp12_from_bytes(&bytes).unwrap();
+        assert!(decoded.equals(&mut e));
+        assert_eq!(fp12_to_bytes(&mut decoded), bytes);
+
+        // Incorrect size
+        assert_eq!(
+            fp12_from_bytes(&bytes[1..]).err(),
+            Some(DecodeError::IncorrectSize)
+        );
+
+        // Coefficient not less than q
+        let mut non_canonical = bytes.clone();
+        for byte in non_canonical[..MODBYTES].iter_mut() {
+            *byte = 0xff;
+        }
+        assert_eq!(
+            fp12_from_bytes(&non_canonical).err(),
+            Some(DecodeError::NonCanonicalEncoding)
+        );
+
+        // Valid FP12 element which is not in GT
+        let mut two = FP12::new_int(2);
+        assert_eq!(
+            fp12_from_bytes(&fp12_to_bytes(&mut two)).err(),
+            Some(DecodeError::NotInSubgroup)
+        );
+    }
+
+    #[test]
+    fn test_is_in_gt_subgroup() {
+        let e = ate_pairing(&GroupG2::generator(), &GroupG1::generator());
+        assert!(is_in_gt_subgroup(&e));
+        assert!(is_in_gt_subgroup(&FP12::new_int(1)));
+        assert!(!is_in_gt_subgroup(&FP12::new_int(2)));
+
+        // The Miller loop output over its conjugate, the first step of the final exponentiation,
+        // is unitary but not in GT
+        let miller = ate(&GroupG2::generator(), &GroupG1::generator());
+        let mut unitary = FP12::new_copy(&miller);
+        unitary.conj();
+        let mut inverse = FP12::new_copy(&miller);
+        inverse.inverse();
+        unitary.mul(&inverse);
+
+        let mut norm = FP12::new_copy(&unitary);
+        norm.conj();
+        norm.mul(&unitary);
+        assert!(norm.isunity());
+        assert!(!is_in_gt_subgroup(&unitary));
+    }
+
     #[test]
     fn compression_decompression_g2_round_trip() {
         // Input 1
@@ -0,0 +1,67 @@
+//! Sizes and constants of the BLS12-381 curve used by this crate.
+//!
+//! Sizes are in bytes. Compressed points are the encodings used by `as_bytes` and `from_bytes`
+//! throughout the crate.
+
+use super::amcl_utils;
+
+pub use amcl_utils::{CURVE_ORDER, G1_BYTE_SIZE, G2_BYTE_SIZE, GT_BYTE_SIZE, MODBYTES};
+
+/// Size of a compressed G1 point, i.e. a PublicKey or AggregatePublicKey.
+pub const G1_COMPRESSED_BYTE_SIZE: usize = MODBYTES;
+
+/// Size of a compressed G2 point, i.e. a Signature or AggregateSignature.
+pub const G2_COMPRESSED_BYTE_SIZE: usize = 2 * MODBYTES;
+
+/// Size of the legacy SecretKey encoding of `as_bytes` and `from_bytes`, the big-endian scalar
+/// padded with leading zeros to the field element size.
+pub const SECRET_KEY_BYTE_SIZE: usize = amcl_utils::MOD_BYTE_SIZE;
+
+/// Size of the standard SecretKey encoding of `to_be_bytes_32` and `from_be_bytes_32`, as used
+/// by eth2 and the IRTF BLS signature draft.
+pub const SECRET_KEY_BE_BYTE_SIZE: usize = 32;
+
+/// The G1 generator compressed.
+pub const G1_GENERATOR_COMPRESSED: [u8; G1_COMPRESSED_BYTE_SIZE] = [
+    0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+];
+
+/// The G2 generator compressed.
+pub const G2_GENERATOR_COMPRESSED: [u8; G2_COMPRESSED_BYTE_SIZE] = [
+    0x93, 0xe0, 0x2b, 0x60, 0x52, 0x71, 0x9f, 0x60, 0x7d, 0xac, 0xd3, 0xa0, 0x88, 0x27, 0x4f, 0x65,
+    0x59, 0x6b, 0xd0, 0xd0, 0x99, 0x20, 0xb6, 0x1a, 0xb5, 0xda, 0x61, 0xbb, 0xdc, 0x7f, 0x50, 0x49,
+    0x33, 0x4c, 0xf1, 0x12, 0x13, 0x94, 0x5d, 0x57, 0xe5, 0xac, 0x7d, 0x05, 0x5d, 0x04, 0x2b, 0x7e,
+    0x02, 0x4a, 0xa2, 0xb2, 0xf0, 0x8f, 0x0a, 0x91, 0x26, 0x08, 0x05, 0x27, 0x2d, 0xc5, 0x10, 0x51,
+    0xc6, 0xe4, 0x7a, 0xd4, 0xfa, 0x40, 0x3b, 0x02, 0xb4, 0x51, 0x0b, 0x64, 0x7a, 0xe3, 0xd1, 0x77,
+    0x0b, 0xac, 0x03, 0x26, 0xa8, 0x05, 0xbb, 0xef, 0xd4, 0x80, 0x56, 0xc8, 0xc1, 0x21, 0xbd, 0xb8,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::super::amcl_utils::{compress_g1, compress_g2, GroupG1, GroupG2};
+    use super::*;
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(G1_COMPRESSED_BYTE_SIZE, 48);
+        assert_eq!(G2_COMPRESSED_BYTE_SIZE, 96);
+        assert_eq!(SECRET_KEY_BYTE_SIZE, 48);
+        assert_eq!(SECRET_KEY_BE_BYTE_SIZE, 32);
+        assert_eq!(G1_BYTE_SIZE, 2 * G1_COMPRESSED_BYTE_SIZE);
+        assert_eq!(G2_BYTE_SIZE, 2 * G2_COMPRESSED_BYTE_SIZE);
+    }
+
+    #[test]
+    fn test_generators() {
+        assert_eq!(
+            compress_g1(&mut GroupG1::generator()),
+            G1_GENERATOR_COMPRESSED.to_vec()
+        );
+        assert_eq!(
+            compress_g2(&mut GroupG2::generator()),
+            G2_GENERATOR_COMPRESSED.to_vec()
+        );
+    }
+}
@@ -1,7 +1,49 @@
+#[cfg(feature = "std")]
+use std::io;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum DecodeError {
     BadPoint,
     IncorrectSize,
     Infinity,
     InvalidCFlag,
+    /// The point is on the curve but not in the prime order subgroup.
+    NotInSubgroup,
+    /// A field element in the encoding is not less than the field modulus.
+    NonCanonicalEncoding,
+    /// The point is infinity where infinity is not accepted.
+    PointAtInfinityDisallowed,
+    /// The input is not valid base64.
+    InvalidBase64,
+    /// The checksum does not match the data.
+    InvalidChecksum,
+}
+
+/// The reason an aggregate verification failed.
+#[derive(Debug, PartialEq, Clone)]
+pub enum VerifyError {
+    /// The number of Messages and PublicKeys differ.
+    MismatchedLengths { msgs: usize, pubkeys: usize },
+    /// There are no Messages or PublicKeys.
+    Empty,
+    /// The Message at this index is not 32 bytes.
+    IncorrectMessageLength(usize),
+    /// The Message at this index equals an earlier Message.
+    DuplicateMessage(usize),
+    /// The inputs are well formed but the pairing check failed.
+    InvalidSignature,
+}
+
+/// The reason signing failed.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SignError {
+    /// The message is longer than the maximum length allowed by the SignerConfig.
+    MessageTooLong { len: usize, max: usize },
+}
+
+#[cfg(feature = "std")]
+impl From<DecodeError> for io::Error {
+    fn from(e: DecodeError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e))
+    }
 }
@@ -0,0 +1,94 @@
+use super::amcl_utils::hash;
+
+/// The domain separation tag eth2 signs with, from the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite.
+pub const ETH2_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// A 32 byte eth2 domain, as returned by `compute_domain` in the consensus spec.
+///
+/// Unlike the `u64` domains used elsewhere in this crate, the domain is not part of the hash to
+/// G2. It is mixed into the signing root instead, which is then hashed with `ETH2_DST`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Eth2Domain(pub [u8; 32]);
+
+impl Eth2Domain {
+    /// Compute the domain from a 4 byte domain type, a fork version and the genesis validators
+    /// root, this is `compute_domain` from the consensus spec.
+    ///
+    /// The domain is the domain type followed by the first 28 bytes of the fork data root,
+    /// `sha256(fork_version || 28 zero bytes || genesis_validators_root)`.
+    pub fn compute(
+        domain_type: [u8; 4],
+        fork_version: [u8; 4],
+        genesis_validators_root: &[u8; 32],
+    ) -> Self {
+        let mut fork_data = [0 as u8; 64];
+        fork_data[..4].copy_from_slice(&fork_version);
+        fork_data[32..].copy_from_slice(genesis_validators_root);
+        let fork_data_root = hash(&fork_data);
+
+        let mut domain = [0 as u8; 32];
+        domain[..4].copy_from_slice(&domain_type);
+        domain[4..].copy_from_slice(&fork_data_root[..28]);
+        Eth2Domain(domain)
+    }
+
+    /// The signing root of an object root in this domain, `sha256(root || domain)`.
+    ///
+    /// This is `compute_signing_root` from the consensus spec, given the object's
+    /// `hash_tree_root`.
+    pub fn signing_root(&self, root: &[u8; 32]) -> [u8; 32] {
+        let mut signing_data = [0 as u8; 64];
+        signing_data[..32].copy_from_slice(root);
+        signing_data[32..].copy_from_slice(&self.0);
+
+        let mut signing_root = [0 as u8; 32];
+        signing_root.copy_from_slice(&hash(&signing_data));
+        signing_root
+    }
+}
+
+impl From<[u8; 32]> for Eth2Domain {
+    fn from(domain: [u8; 32]) -> Self {
+        Eth2Domain(domain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+
+    use super::*;
+
+    #[test]
+    fn test_compute_domain() {
+        // DOMAIN_DEPOSIT with the mainnet genesis fork version and an empty root
+        let domain = Eth2Domain::compute([3, 0, 0, 0], [0; 4], &[0; 32]);
+        assert_eq!(
+            domain.0.to_vec(),
+            hex::decode("03000000f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9")
+                .unwrap()
+        );
+
+        assert_ne!(
+            Eth2Domain::compute([3, 0, 0, 0], [0, 0, 0, 1], &[0; 32]),
+            domain
+        );
+        assert_ne!(Eth2Domain::compute([3, 0, 0, 0], [0; 4], &[1; 32]), domain);
+        assert_ne!(Eth2Domain::compute([4, 0, 0, 0], [0; 4], &[0; 32]), domain);
+    }
+
+    #[test]
+    fn test_signing_root() {
+        let domain = Eth2Domain::compute([3, 0, 0, 0], [0; 4], &[0; 32]);
+        let mut root = [0 as u8; 32];
+        for (i, byte) in root.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            domain.signing_root(&root).to_vec(),
+            hex::decode("11fe0663eb697f80f9146952bb713df7d30f2e8f0f895446945452e580d11ba1")
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Round trip entry points for fuzz targets.
+//!
+//! Each function decodes the input and, if it decodes, re-encodes it and panics unless the
+//! bytes are unchanged. Any accepted encoding must therefore be canonical. A cargo-fuzz target
+//! only needs to pass the fuzzer's data to one of these.
+
+use super::keys::{PublicKey, SecretKey};
+use super::signature::Signature;
+
+/// Round trip bytes through `PublicKey::from_bytes` and `PublicKey::as_bytes`.
+pub fn fuzz_roundtrip_pubkey(bytes: &[u8]) {
+    if let Ok(pk) = PublicKey::from_bytes(bytes) {
+        assert_eq!(&pk.as_bytes()[..], bytes, "PublicKey did not round trip");
+    }
+}
+
+/// Round trip bytes through `Signature::from_bytes` and `Signature::as_bytes`.
+pub fn fuzz_roundtrip_signature(bytes: &[u8]) {
+    if let Ok(sig) = Signature::from_bytes(bytes) {
+        assert_eq!(&sig.as_bytes()[..], bytes, "Signature did not round trip");
+    }
+}
+
+/// Round trip bytes through `SecretKey::from_bytes` and `SecretKey::as_bytes`.
+pub fn fuzz_roundtrip_secret(bytes: &[u8]) {
+    if let Ok(sk) = SecretKey::from_bytes(bytes) {
+        assert_eq!(&sk.as_bytes()[..], bytes, "SecretKey did not round trip");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use self::rand::Rng;
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_fuzz_roundtrip_valid() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        fuzz_roundtrip_pubkey(&keypair.pk.as_bytes());
+        fuzz_roundtrip_pubkey(&PublicKey::identity().as_bytes());
+        fuzz_roundtrip_signature(&Signature::new(&[42; 32], 1, &keypair.sk).as_bytes());
+        fuzz_roundtrip_signature(&Signature::identity().as_bytes());
+        fuzz_roundtrip_secret(&keypair.sk.as_bytes());
+    }
+
+    #[test]
+    fn test_fuzz_roundtrip_random() {
+        let mut rng = rand::thread_rng();
+        for len in &[0, 1, 32, 47, 48, 49, 95, 96, 97] {
+            for _ in 0..10 {
+                let mut bytes = vec![0; *len];
+                rng.fill(&mut bytes[..]);
+                fuzz_roundtrip_pubkey(&bytes);
+                fuzz_roundtrip_signature(&bytes);
+                fuzz_roundtrip_secret(&bytes);
+
+                // With the compression flag set so more inputs reach the point decoding
+                if !bytes.is_empty() {
+                    bytes[0] |= 0x80;
+                    fuzz_roundtrip_pubkey(&bytes);
+                    fuzz_roundtrip_signature(&bytes);
+                }
+            }
+        }
+    }
+}
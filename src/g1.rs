@@ -1,7 +1,9 @@
-use super::amcl_utils::{compress_g1, decompress_g1, BigNum, GroupG1};
+use super::amcl_utils::{compress_g1, decompress_g1, is_in_g1_subgroup, BigNum, GroupG1};
 use super::errors::DecodeError;
 #[cfg(feature = "std")]
 use std::fmt;
+#[cfg(feature = "ct")]
+use subtle::Choice;
 
 pub trait G1Wrapper {
     fn point(&self) -> &G1Point;
@@ -30,6 +32,10 @@ impl G1Point {
         self.point.affine();
     }
 
+    pub fn neg(&mut self) {
+        self.point.neg();
+    }
+
     pub fn is_infinity(&self) -> bool {
         self.point.is_infinity()
     }
@@ -46,6 +52,14 @@ impl G1Point {
         self.point
     }
 
+    /// Select `a` if `choice` is 0 or `b` if `choice` is 1, in constant time.
+    #[cfg(feature = "ct")]
+    pub fn conditional_select(a: &G1Point, b: &G1Point, choice: Choice) -> Self {
+        let mut point = a.point;
+        point.cmove(&b.point, choice.unwrap_u8() as isize);
+        Self { point }
+    }
+
     pub fn getx(&mut self) -> BigNum {
         self.point.getx()
     }
@@ -55,7 +69,22 @@ impl G1Point {
     }
 
     /// Instatiate the G1 point from compressed bytes.
+    ///
+    /// The point must be on the curve and in the correct subgroup.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let pt = decompress_g1(&bytes)?;
+        if !is_in_g1_subgroup(&pt) {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(Self { point: pt })
+    }
+
+    /// Instatiate the G1 point from compressed bytes without a subgroup check.
+    ///
+    /// The point must still be on the curve, but the (expensive) subgroup check is skipped.
+    /// Accepting a point outside the subgroup breaks the security of the scheme, so this must
+    /// only be used on bytes which have previously passed `from_bytes`.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, DecodeError> {
         let pt = decompress_g1(&bytes)?;
         Ok(Self { point: pt })
     }
@@ -83,6 +112,8 @@ impl Clone for G1Point {
     }
 }
 
+// `equals` compares projective coordinates by cross multiplying, so no conversion to affine
+// is needed.
 impl PartialEq for G1Point {
     fn eq(&self, other: &G1Point) -> bool {
         let mut clone_a = self.clone();
@@ -99,3 +130,28 @@ impl Default for G1Point {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq_projective() {
+        // G + G is left in projective coordinates, 2 * G is affine
+        let mut sum = G1Point::from_raw(GroupG1::generator());
+        sum.add(&G1Point::from_raw(GroupG1::generator()));
+        let mut double = GroupG1::generator().mul(&BigNum::new_int(2));
+        double.affine();
+        assert_eq!(sum, G1Point::from_raw(double));
+        assert_ne!(sum, G1Point::from_raw(GroupG1::generator()));
+    }
+
+    #[cfg(feature = "ct")]
+    #[test]
+    fn test_conditional_select() {
+        let a = G1Point::new();
+        let b = G1Point::from_raw(GroupG1::generator());
+        assert_eq!(G1Point::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(G1Point::conditional_select(&a, &b, Choice::from(1)), b);
+    }
+}
@@ -1,7 +1,11 @@
-use super::amcl_utils::{compress_g2, decompress_g2, GroupG2};
+use super::amcl_utils::{compress_g2, decompress_g2, hash_on_g2, is_in_g2_subgroup, GroupG2};
 use super::errors::DecodeError;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "std")]
 use std::fmt;
+#[cfg(feature = "ct")]
+use subtle::Choice;
 
 pub struct G2Point {
     point: GroupG2,
@@ -26,6 +30,10 @@ impl G2Point {
         self.point.affine();
     }
 
+    pub fn neg(&mut self) {
+        self.point.neg();
+    }
+
     pub fn is_infinity(&self) -> bool {
         self.point.is_infinity()
     }
@@ -42,8 +50,31 @@ impl G2Point {
         self.point
     }
 
+    /// Select `a` if `choice` is 0 or `b` if `choice` is 1, in constant time.
+    #[cfg(feature = "ct")]
+    pub fn conditional_select(a: &G2Point, b: &G2Point, choice: Choice) -> Self {
+        let mut point = a.point;
+        point.cmove(&b.point, choice.unwrap_u8() as isize);
+        Self { point }
+    }
+
     /// Instatiate the point from compressed bytes.
+    ///
+    /// The point must be on the curve and in the correct subgroup.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let pt = decompress_g2(&bytes)?;
+        if !is_in_g2_subgroup(&pt) {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(Self { point: pt })
+    }
+
+    /// Instatiate the point from compressed bytes without a subgroup check.
+    ///
+    /// The point must still be on the curve, but the (expensive) subgroup check is skipped.
+    /// Accepting a point outside the subgroup breaks the security of the scheme, so this must
+    /// only be used on bytes which have previously passed `from_bytes`.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, DecodeError> {
         let pt = decompress_g2(&bytes)?;
         Ok(Self { point: pt })
     }
@@ -71,6 +102,8 @@ impl Clone for G2Point {
     }
 }
 
+// `equals` compares projective coordinates by cross multiplying, so no conversion to affine
+// is needed.
 impl PartialEq for G2Point {
     fn eq(&self, other: &G2Point) -> bool {
         let mut clone_a = self.clone();
@@ -87,3 +120,62 @@ impl Default for G2Point {
         Self::new()
     }
 }
+
+/// Hash many messages to G2 with the same domain, see `hash_on_g2`.
+///
+/// With the `rayon` feature the messages are hashed in parallel. The points are returned in
+/// the same order as the messages either way.
+pub fn hash_many_on_g2(msgs: &[&[u8]], domain: u64) -> Vec<G2Point> {
+    #[cfg(feature = "rayon")]
+    let msgs_iter = msgs.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let msgs_iter = msgs.iter();
+
+    msgs_iter
+        .map(|msg| {
+            let mut point = hash_on_g2(msg, domain);
+            point.affine();
+            G2Point::from_raw(point)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq_projective() {
+        // H + H is left in projective coordinates
+        let point = hash_on_g2(b"cats", 42);
+        let mut sum = G2Point::from_raw(point);
+        sum.add(&G2Point::from_raw(point));
+        let mut double = point;
+        double.dbl();
+        double.affine();
+        assert_eq!(sum, G2Point::from_raw(double));
+        assert_ne!(sum, G2Point::from_raw(point));
+    }
+
+    #[test]
+    fn test_hash_many_on_g2() {
+        let msgs: Vec<Vec<u8>> = (0..10).map(|i| vec![i; 32]).collect();
+        let msg_refs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+
+        let points = hash_many_on_g2(&msg_refs, 42);
+        assert_eq!(points.len(), msgs.len());
+        for (point, msg) in points.iter().zip(msgs.iter()) {
+            assert_eq!(*point, G2Point::from_raw(hash_on_g2(msg, 42)));
+        }
+        assert!(hash_many_on_g2(&[], 42).is_empty());
+    }
+
+    #[cfg(feature = "ct")]
+    #[test]
+    fn test_conditional_select() {
+        let a = G2Point::new();
+        let b = G2Point::from_raw(GroupG2::generator());
+        assert_eq!(G2Point::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(G2Point::conditional_select(&a, &b, Choice::from(1)), b);
+    }
+}
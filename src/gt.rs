@@ -0,0 +1,182 @@
+use super::amcl_utils::{
+    ate_pairing, fp12_from_bytes, fp12_to_bytes, generator_g1_negative, hash_on_g2, FP12,
+};
+use super::errors::DecodeError;
+use super::g1::G1Point;
+use super::g2::G2Point;
+use super::keys::PublicKey;
+use super::signature::Signature;
+use amcl::bls381::pair;
+#[cfg(feature = "std")]
+use std::fmt;
+
+/// An element of GT, the target group of the pairing.
+pub struct Gt {
+    point: FP12,
+}
+
+impl Gt {
+    /// The identity element of GT.
+    pub fn one() -> Self {
+        Self {
+            point: FP12::new_int(1),
+        }
+    }
+
+    /// Compute the pairing e(P, Q) of a G1 and a G2 point.
+    pub fn pairing(g1: &G1Point, g2: &G2Point) -> Self {
+        Self {
+            point: ate_pairing(g2.as_raw(), g1.as_raw()),
+        }
+    }
+
+    pub fn from_raw(point: FP12) -> Self {
+        Self { point }
+    }
+
+    pub fn as_raw(&self) -> &FP12 {
+        &self.point
+    }
+
+    /// Instatiate the element from its 576 byte encoding.
+    ///
+    /// Each coefficient must be less than the field modulus and the element must be in GT.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let point = fp12_from_bytes(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Export (serialize) the element to its 576 byte encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut clone = FP12::new_copy(&self.point);
+        fp12_to_bytes(&mut clone)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for Gt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut temp = FP12::new_copy(&self.point);
+        write!(f, "{}", temp.tostring())
+    }
+}
+
+impl Clone for Gt {
+    fn clone(&self) -> Self {
+        Self {
+            point: FP12::new_copy(&self.point),
+        }
+    }
+}
+
+impl PartialEq for Gt {
+    fn eq(&self, other: &Gt) -> bool {
+        let mut clone_a = self.clone();
+        let mut clone_b = other.clone();
+
+        clone_a.point.equals(&mut clone_b.point)
+    }
+}
+
+impl Eq for Gt {}
+
+/// Check the product of pairings e(P_1, Q_1) * ... * e(P_n, Q_n) is one.
+///
+/// Every verification equation in this crate is an instance of this, e.g. `Signature::verify`
+/// checks e(-G1, S) * e(PK, H(m)) == 1. Negating points as required is left to the caller.
+/// Pairs containing the point at infinity contribute one and are skipped.
+pub fn check_pairing_product(pairs: &[(&G1Point, &G2Point)]) -> bool {
+    let mut r = pair::initmp();
+    for (g1, g2) in pairs {
+        if g1.is_infinity() || g2.is_infinity() {
+            continue;
+        }
+        let mut g1 = (*g1).clone();
+        let mut g2 = (*g2).clone();
+        g1.affine();
+        g2.affine();
+        pair::another(&mut r, g2.as_raw(), g1.as_raw());
+    }
+
+    let mut v = pair::miller(&r);
+    v = pair::fexp(&v);
+    v.isunity()
+}
+
+/// The intermediate values of the pairing check in `Signature::verify`, for mirroring the
+/// computation elsewhere, e.g. in a circuit.
+///
+/// Returns the output of the Miller loop of e(S, -G1) * e(H(m), PK), and that value after the
+/// final exponentiation. The Signature verifies if and only if the second is one (`Gt::one()`)
+/// and the PublicKey is not the identity. The first is not in GT so both are raw FP12 values.
+pub fn verification_witness(
+    pk: &PublicKey,
+    msg: &[u8],
+    domain: u64,
+    sig: &Signature,
+) -> (FP12, FP12) {
+    let mut msg_hash_point = hash_on_g2(msg, domain);
+    msg_hash_point.affine();
+    let generator_g1_negative = generator_g1_negative();
+
+    let miller_out = pair::ate2(
+        sig.point.as_raw(),
+        &generator_g1_negative,
+        &msg_hash_point,
+        pk.point.as_raw(),
+    );
+    let final_exp = pair::fexp(&miller_out);
+    (miller_out, final_exp)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_check_pairing_product() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+        let msg_hash = G2Point::from_raw(hash_on_g2(b"cats", 42));
+        let negative_g1 = G1Point::from_raw(generator_g1_negative());
+
+        // e(-G1, S) * e(PK, H(m)) == 1
+        assert!(check_pairing_product(&[
+            (&negative_g1, &signature.point),
+            (&keypair.pk.point, &msg_hash),
+        ]));
+
+        // Wrong message
+        let other_hash = G2Point::from_raw(hash_on_g2(b"dogs", 42));
+        assert!(!check_pairing_product(&[
+            (&negative_g1, &signature.point),
+            (&keypair.pk.point, &other_hash),
+        ]));
+
+        // The empty product and infinity pairs are one
+        assert!(check_pairing_product(&[]));
+        assert!(check_pairing_product(&[(&G1Point::new(), &msg_hash)]));
+        assert!(!check_pairing_product(&[(&keypair.pk.point, &msg_hash)]));
+    }
+
+    #[test]
+    fn test_verification_witness() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+
+        let (mut miller_out, mut final_exp) =
+            verification_witness(&keypair.pk, b"cats", 42, &signature);
+        assert!(!miller_out.isunity());
+        assert!(pair::fexp(&miller_out).equals(&mut final_exp));
+        assert!(final_exp.isunity());
+        assert_eq!(Gt::from_raw(final_exp), Gt::one());
+
+        // Agrees with verify on a failure
+        let (_, mut final_exp) = verification_witness(&keypair.pk, b"dogs", 42, &signature);
+        assert!(!final_exp.isunity());
+        assert!(!signature.verify(b"dogs", 42, &keypair.pk));
+    }
+}
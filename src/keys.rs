@@ -1,15 +1,30 @@
 extern crate amcl;
+#[cfg(feature = "base64")]
+extern crate base64;
 extern crate rand;
 extern crate zeroize;
 
 use self::zeroize::Zeroize;
 use super::amcl_utils::{self, BigNum, GroupG1, CURVE_ORDER, MOD_BYTE_SIZE};
+use super::constants::SECRET_KEY_BE_BYTE_SIZE;
 use super::errors::DecodeError;
 use super::g1::{G1Point, G1Wrapper};
+use super::g2::G2Point;
+#[cfg(feature = "getrandom")]
+use super::rng::get_getrandom_seeded_rng;
 use super::rng::get_seeded_rng;
-use rand::Rng;
+use super::scheme::{MinPubkey, Scheme};
+use super::signature::Signature;
+#[cfg(not(feature = "std"))]
+use core::{fmt, hash};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "std")]
-use std::fmt;
+use std::{fmt, hash, io};
+
+// Length of the checksum appended by `SecretKey::to_checksummed_bytes`.
+const CHECKSUM_BYTES: usize = 4;
 
 #[derive(Clone)]
 /// A BLS secret key.
@@ -19,13 +34,30 @@ pub struct SecretKey {
 
 impl SecretKey {
     /// Generate a new SecretKey using an Rng to seed the `amcl::rand::RAND` PRNG.
-    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    ///
+    /// The Rng must be cryptographically secure, a seeded CSPRNG may be used for reproducible
+    /// keys in tests.
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let mut r = get_seeded_rng(rng, 256);
         let x = BigNum::randomnum(&BigNum::new_ints(&CURVE_ORDER), &mut r);
         SecretKey { x }
     }
 
+    /// Generate a new SecretKey using entropy from `getrandom`, without an Rng.
+    ///
+    /// Panics if the platform has no entropy source.
+    #[cfg(feature = "getrandom")]
+    pub fn generate() -> Self {
+        let mut r = get_getrandom_seeded_rng(256);
+        let x = BigNum::randomnum(&BigNum::new_ints(&CURVE_ORDER), &mut r);
+        SecretKey { x }
+    }
+
     /// Instantiate a SecretKey from existing bytes.
+    ///
+    /// The bytes are the 48 byte big-endian form of `as_bytes`, the scalar padded to the field
+    /// element size. Keys from eth2 or the IRTF BLS signature draft are 32 bytes, see
+    /// `from_be_bytes_32`.
     pub fn from_bytes(bytes: &[u8]) -> Result<SecretKey, DecodeError> {
         if bytes.len() != MOD_BYTE_SIZE {
             return Err(DecodeError::IncorrectSize);
@@ -36,12 +68,127 @@ impl SecretKey {
     }
 
     /// Export the SecretKey to bytes.
+    ///
+    /// These are 48 big-endian bytes, for a key less than the curve order the first 16 are zero.
+    /// For the standard 32 byte encoding see `to_be_bytes_32`.
     pub fn as_bytes(&self) -> Vec<u8> {
         let mut temp = BigNum::new_copy(&self.x);
         let mut bytes: [u8; MOD_BYTE_SIZE] = [0; MOD_BYTE_SIZE];
         temp.tobytes(&mut bytes);
         bytes.to_vec()
     }
+
+    /// Instantiate a SecretKey from exactly 32 big-endian bytes, the standard encoding used by
+    /// eth2 and the IRTF BLS signature draft.
+    ///
+    /// This is `from_bytes` without the 16 leading zero bytes. The value must be less than the
+    /// curve order, otherwise `DecodeError::NonCanonicalEncoding` is returned.
+    pub fn from_be_bytes_32(bytes: &[u8]) -> Result<SecretKey, DecodeError> {
+        if bytes.len() != SECRET_KEY_BE_BYTE_SIZE {
+            return Err(DecodeError::IncorrectSize);
+        }
+        let mut padded = [0 as u8; MOD_BYTE_SIZE];
+        padded[MOD_BYTE_SIZE - SECRET_KEY_BE_BYTE_SIZE..].copy_from_slice(bytes);
+        let mut scalar = BigNum::frombytes(&padded);
+        padded.zeroize();
+        let sk = SecretKey::from_scalar(&scalar);
+        scalar.w.zeroize();
+        sk
+    }
+
+    /// Export the SecretKey to exactly 32 big-endian bytes, the standard encoding used by eth2
+    /// and the IRTF BLS signature draft.
+    ///
+    /// The key is first reduced modulo the curve order, so it always fits. For keys less than
+    /// the curve order these are the last 32 bytes of `as_bytes`.
+    pub fn to_be_bytes_32(&self) -> [u8; SECRET_KEY_BE_BYTE_SIZE] {
+        let mut scalar = self.as_scalar();
+        let mut padded = [0 as u8; MOD_BYTE_SIZE];
+        scalar.tobytes(&mut padded);
+        scalar.w.zeroize();
+
+        let mut bytes = [0 as u8; SECRET_KEY_BE_BYTE_SIZE];
+        bytes.copy_from_slice(&padded[MOD_BYTE_SIZE - SECRET_KEY_BE_BYTE_SIZE..]);
+        padded.zeroize();
+        bytes
+    }
+
+    /// Instantiate a SecretKey from a scalar, which must be less than the curve order.
+    pub fn from_scalar(scalar: &BigNum) -> Result<SecretKey, DecodeError> {
+        let mut x = BigNum::new_copy(scalar);
+        x.norm();
+        if BigNum::comp(&x, &BigNum::new_ints(&CURVE_ORDER)) >= 0 {
+            x.w.zeroize();
+            return Err(DecodeError::NonCanonicalEncoding);
+        }
+        Ok(SecretKey { x })
+    }
+
+    /// Export the SecretKey as a scalar reduced modulo the curve order.
+    ///
+    /// The returned BigNum is a copy of the secret, the caller is responsible for zeroizing it.
+    pub fn as_scalar(&self) -> BigNum {
+        let mut x = BigNum::new_copy(&self.x);
+        x.rmod(&BigNum::new_ints(&CURVE_ORDER));
+        x
+    }
+
+    /// Export the SecretKey to its 32 big-endian bytes followed by a 4 byte checksum, the first
+    /// 4 bytes of the SHA256 hash of those 32 bytes.
+    pub fn to_checksummed_bytes(&self) -> [u8; SECRET_KEY_BE_BYTE_SIZE + CHECKSUM_BYTES] {
+        let mut sk_bytes = self.to_be_bytes_32();
+        let mut bytes = [0; SECRET_KEY_BE_BYTE_SIZE + CHECKSUM_BYTES];
+        bytes[..SECRET_KEY_BE_BYTE_SIZE].copy_from_slice(&sk_bytes);
+        bytes[SECRET_KEY_BE_BYTE_SIZE..]
+            .copy_from_slice(&amcl_utils::hash(&sk_bytes)[..CHECKSUM_BYTES]);
+        sk_bytes.zeroize();
+        bytes
+    }
+
+    /// Instantiate a SecretKey from bytes created by `to_checksummed_bytes`.
+    ///
+    /// Returns `DecodeError::InvalidChecksum` if the checksum does not match.
+    pub fn from_checksummed_bytes(bytes: &[u8]) -> Result<SecretKey, DecodeError> {
+        if bytes.len() != SECRET_KEY_BE_BYTE_SIZE + CHECKSUM_BYTES {
+            return Err(DecodeError::IncorrectSize);
+        }
+        let (sk_bytes, checksum) = bytes.split_at(SECRET_KEY_BE_BYTE_SIZE);
+        if amcl_utils::hash(sk_bytes)[..CHECKSUM_BYTES] != *checksum {
+            return Err(DecodeError::InvalidChecksum);
+        }
+        SecretKey::from_be_bytes_32(sk_bytes)
+    }
+
+    /// Add two SecretKeys modulo the curve order, e.g. to combine shares in a DKG.
+    pub fn add(&self, other: &SecretKey) -> SecretKey {
+        let order = BigNum::new_ints(&CURVE_ORDER);
+        let mut x = BigNum::new_copy(&self.x);
+        x.rmod(&order);
+        x.add(&other.x);
+        x.norm();
+        x.rmod(&order);
+        SecretKey { x }
+    }
+
+    /// Multiply two SecretKeys modulo the curve order.
+    pub fn mul(&self, other: &SecretKey) -> SecretKey {
+        let order = BigNum::new_ints(&CURVE_ORDER);
+        SecretKey {
+            x: BigNum::modmul(&self.x, &other.x, &order),
+        }
+    }
+
+    /// Sign a message which has already been hashed to a G2 point.
+    ///
+    /// The point is multiplied by the secret key, no hashing takes place. If the point was
+    /// produced by `hash_on_g2` the Signature is identical to `Signature::new`.
+    pub fn sign_prehashed(&self, hash_point: &G2Point) -> Signature {
+        let mut sig = hash_point.as_raw().mul(&self.x);
+        sig.affine();
+        Signature {
+            point: G2Point::from_raw(sig),
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -74,10 +221,11 @@ impl Drop for SecretKey {
 }
 
 /// A BLS public key.
+///
+/// The Scheme defaults to `MinPubkey`, PublicKeys in G1, which is the only Scheme implemented.
 #[derive(Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
-pub struct PublicKey {
-    pub point: G1Point,
+pub struct PublicKey<S: Scheme = MinPubkey> {
+    pub point: S::PublicKeyPoint,
 }
 
 impl PublicKey {
@@ -87,7 +235,7 @@ impl PublicKey {
             point: {
                 #[cfg(feature = "std")]
                 {
-                    G1Point::from_raw(amcl_utils::GENERATORG1.mul(&sk.x))
+                    G1Point::from_raw(amcl_utils::g1_generator_mul(&sk.x))
                 }
                 #[cfg(not(feature = "std"))]
                 {
@@ -97,6 +245,49 @@ impl PublicKey {
         }
     }
 
+    /// The PublicKey of the G1 generator, equivalently of the SecretKey one.
+    pub fn generator() -> Self {
+        PublicKey {
+            point: {
+                #[cfg(feature = "std")]
+                {
+                    G1Point::from_raw(*amcl_utils::GENERATORG1)
+                }
+                #[cfg(not(feature = "std"))]
+                {
+                    G1Point::from_raw(amcl_utils::GroupG1::generator())
+                }
+            },
+        }
+    }
+
+    /// The PublicKey of the point at infinity, the identity of G1.
+    ///
+    /// This is not a valid PublicKey, see `key_validate`.
+    pub fn identity() -> Self {
+        PublicKey {
+            point: G1Point::new(),
+        }
+    }
+
+    /// The negation of `PublicKey::generator()`, as paired with Signatures during verification.
+    pub fn negative_generator() -> Self {
+        PublicKey {
+            point: G1Point::from_raw(amcl_utils::generator_g1_negative()),
+        }
+    }
+
+    /// Instantiate the PublicKey of the sum of some SecretKeys.
+    ///
+    /// This equals the aggregate of the PublicKeys of each SecretKey.
+    pub fn from_secret_keys_sum(sks: &[&SecretKey]) -> Self {
+        let mut sum = SecretKey { x: BigNum::new() };
+        for sk in sks {
+            sum = sum.add(sk);
+        }
+        PublicKey::from_secret_key(&sum)
+    }
+
     /// Instantiate a PublicKey from some GroupG1 point.
     pub fn new_from_raw(pt: &GroupG1) -> Self {
         PublicKey {
@@ -104,18 +295,174 @@ impl PublicKey {
         }
     }
 
+    /// Instantiate a PublicKey from a G1Point.
+    ///
+    /// The point is not checked, see `key_validate`.
+    pub fn from_g1(point: G1Point) -> Self {
+        PublicKey { point }
+    }
+
+    /// Instantiate a PublicKey from a raw `amcl` G1 point.
+    ///
+    /// If `check_subgroup` is true the point must be in the G1 subgroup, otherwise it is not
+    /// checked, see `key_validate`.
+    pub fn from_g1_raw(point: GroupG1, check_subgroup: bool) -> Result<Self, DecodeError> {
+        if check_subgroup && !amcl_utils::is_in_g1_subgroup(&point) {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(PublicKey {
+            point: G1Point::from_raw(point),
+        })
+    }
+
+    /// Convert the PublicKey into its raw `amcl` G1 point.
+    pub fn into_g1_raw(self) -> GroupG1 {
+        self.point.into_raw()
+    }
+
+    /// The G1Point of the PublicKey.
+    pub fn as_g1(&self) -> &G1Point {
+        &self.point
+    }
+
+    /// Convert the PublicKey into its G1Point.
+    pub fn into_g1(self) -> G1Point {
+        self.point
+    }
+
     /// Instantiate a PublicKey from compressed bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, DecodeError> {
         let point = G1Point::from_bytes(bytes)?;
         Ok(Self { point })
     }
 
+    /// Instantiate PublicKeys from many compressed byte slices, e.g. a committee.
+    ///
+    /// Every slice is decoded before any subgroup check is made, so malformed input is rejected
+    /// without paying for the expensive checks. The subgroup checks are then run on the decoded
+    /// points, in parallel with the `rayon` feature. On failure the index of the first invalid
+    /// slice is returned with its error, as if each slice had been passed to `from_bytes` in
+    /// order.
+    pub fn batch_from_bytes(blobs: &[&[u8]]) -> Result<Vec<PublicKey>, (usize, DecodeError)> {
+        let mut keys = Vec::with_capacity(blobs.len());
+        let mut decode_error = None;
+        for (i, bytes) in blobs.iter().enumerate() {
+            match Self::from_bytes_unchecked(bytes) {
+                Ok(key) => keys.push(key),
+                Err(e) => {
+                    decode_error = Some((i, e));
+                    break;
+                }
+            }
+        }
+
+        // Only keys before a decoding failure can fail earlier
+        #[cfg(feature = "rayon")]
+        let not_in_subgroup = keys
+            .par_iter()
+            .position_first(|key| !key.is_in_correct_subgroup());
+        #[cfg(not(feature = "rayon"))]
+        let not_in_subgroup = keys.iter().position(|key| !key.is_in_correct_subgroup());
+
+        if let Some(i) = not_in_subgroup {
+            return Err((i, DecodeError::NotInSubgroup));
+        }
+        match decode_error {
+            Some(error) => Err(error),
+            None => Ok(keys),
+        }
+    }
+
+    /// Instantiate a PublicKey from compressed bytes, skipping the subgroup check.
+    ///
+    /// Only use this for bytes which are already known to be a valid PublicKey (see
+    /// `G1Point::from_bytes_unchecked`).
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<PublicKey, DecodeError> {
+        let point = G1Point::from_bytes_unchecked(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// The negation of the PublicKey, i.e. the PublicKey of the negated SecretKey.
+    pub fn neg(&self) -> PublicKey {
+        let mut point = self.point.clone();
+        point.neg();
+        PublicKey { point }
+    }
+
+    /// Check the PublicKey is in the G1 subgroup of order r.
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        amcl_utils::is_in_g1_subgroup(self.point.as_raw())
+    }
+
+    /// Check the PublicKey is a valid key, this is `KeyValidate` from the IRTF BLS signature draft.
+    ///
+    /// The PublicKey must not be the point at infinity and must be in the G1 subgroup.
+    pub fn key_validate(&self) -> Result<(), DecodeError> {
+        if self.point.is_infinity() {
+            return Err(DecodeError::PointAtInfinityDisallowed);
+        }
+        if !self.is_in_correct_subgroup() {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    /// Check every PublicKey is in the G1 subgroup and is not the point at infinity.
+    ///
+    /// Returns the indices of all invalid PublicKeys, in order, if any fail.
+    pub fn batch_key_validate(keys: &[&PublicKey]) -> Result<(), Vec<usize>> {
+        let invalid: Vec<usize> = keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| key.key_validate().is_err())
+            .map(|(i, _)| i)
+            .collect();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid)
+        }
+    }
+
     /// Export the PublicKey to compressed bytes.
     pub fn as_bytes(&self) -> Vec<u8> {
         let mut clone = self.point.clone();
         clone.as_bytes()
     }
 
+    /// Encode the compressed bytes as standard padded base64.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.as_bytes())
+    }
+
+    /// Instantiate a PublicKey from standard padded base64 of the compressed bytes.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(encoded: &str) -> Result<PublicKey, DecodeError> {
+        let bytes = base64::decode(encoded).map_err(|_| DecodeError::InvalidBase64)?;
+        if bytes.len() != amcl_utils::MODBYTES {
+            return Err(DecodeError::IncorrectSize);
+        }
+        Self::from_bytes(&bytes)
+    }
+
+    /// Write the compressed bytes to some `io::Write`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_bytes())
+    }
+
+    /// Read exactly 48 compressed bytes from some `io::Read` and decode them.
+    ///
+    /// Decoding failures are returned as `io::ErrorKind::InvalidData`.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<PublicKey> {
+        let mut bytes = [0; amcl_utils::MODBYTES];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+
     /// Export the public key to uncompress (x, y) bytes
     pub fn as_uncompressed_bytes(&mut self) -> Vec<u8> {
         if self.point.is_infinity() {
@@ -156,11 +503,112 @@ impl PublicKey {
         if point.is_infinity() {
             return Err(DecodeError::BadPoint);
         }
+        if !amcl_utils::is_in_g1_subgroup(&point) {
+            return Err(DecodeError::NotInSubgroup);
+        }
 
         Ok(PublicKey::new_from_raw(&point))
     }
 }
 
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        amcl_utils::fmt_hex(f, &self.as_bytes())
+    }
+}
+
+/// A PublicKey stored along with its compressed bytes.
+///
+/// The bytes are computed once when the CachedPublicKey is created, so repeated serialization,
+/// hashing and equality copy them instead of recompressing the point. The PublicKey can only be
+/// replaced through `set_public_key`, which recomputes the bytes, so they always match the point.
+#[derive(Clone)]
+pub struct CachedPublicKey {
+    public_key: PublicKey,
+    compressed: [u8; amcl_utils::MODBYTES],
+}
+
+impl CachedPublicKey {
+    /// Wrap a PublicKey, compressing it.
+    pub fn new(public_key: PublicKey) -> Self {
+        let compressed = Self::compress(&public_key);
+        Self {
+            public_key,
+            compressed,
+        }
+    }
+
+    /// Instantiate a CachedPublicKey from compressed bytes, see `PublicKey::from_bytes`.
+    ///
+    /// Only canonical bytes decode, so they are stored as they are.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let public_key = PublicKey::from_bytes(bytes)?;
+        let mut compressed = [0 as u8; amcl_utils::MODBYTES];
+        compressed.copy_from_slice(bytes);
+        Ok(Self {
+            public_key,
+            compressed,
+        })
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Replace the PublicKey, compressing the new one.
+    pub fn set_public_key(&mut self, public_key: PublicKey) {
+        self.compressed = Self::compress(&public_key);
+        self.public_key = public_key;
+    }
+
+    pub fn into_public_key(self) -> PublicKey {
+        self.public_key
+    }
+
+    /// The compressed bytes.
+    pub fn compressed(&self) -> [u8; amcl_utils::MODBYTES] {
+        self.compressed
+    }
+
+    /// Export the PublicKey to compressed bytes, see `compressed`.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.compressed.to_vec()
+    }
+
+    fn compress(public_key: &PublicKey) -> [u8; amcl_utils::MODBYTES] {
+        let mut compressed = [0 as u8; amcl_utils::MODBYTES];
+        compressed.copy_from_slice(&public_key.as_bytes());
+        compressed
+    }
+}
+
+impl From<PublicKey> for CachedPublicKey {
+    fn from(public_key: PublicKey) -> Self {
+        Self::new(public_key)
+    }
+}
+
+// Compressed bytes are unique to a point, so comparing them compares the PublicKeys.
+impl PartialEq for CachedPublicKey {
+    fn eq(&self, other: &CachedPublicKey) -> bool {
+        self.compressed[..] == other.compressed[..]
+    }
+}
+
+impl Eq for CachedPublicKey {}
+
+impl hash::Hash for CachedPublicKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.compressed[..].hash(state);
+    }
+}
+
+impl fmt::Debug for CachedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        amcl_utils::fmt_hex(f, &self.compressed)
+    }
+}
+
 /// A helper which stores a BLS public and private key pair.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -171,11 +619,49 @@ pub struct Keypair {
 
 impl Keypair {
     /// Instantiate a Keypair using SecretKey::random().
-    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let sk = SecretKey::random(rng);
         let pk = PublicKey::from_secret_key(&sk);
         Keypair { sk, pk }
     }
+
+    /// Instantiate a Keypair using SecretKey::generate(), which takes entropy from `getrandom`.
+    ///
+    /// Panics if the platform has no entropy source.
+    #[cfg(feature = "getrandom")]
+    pub fn generate() -> Self {
+        let sk = SecretKey::generate();
+        let pk = PublicKey::from_secret_key(&sk);
+        Keypair { sk, pk }
+    }
+
+    /// Instantiate a Keypair from the 32 big-endian SecretKey bytes, deriving the PublicKey.
+    ///
+    /// See `SecretKey::from_be_bytes_32`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Keypair, DecodeError> {
+        let sk = SecretKey::from_be_bytes_32(bytes)?;
+        let pk = PublicKey::from_secret_key(&sk);
+        Ok(Keypair { sk, pk })
+    }
+
+    /// Export the Keypair to bytes, this is only the 32 big-endian SecretKey bytes.
+    ///
+    /// See `SecretKey::to_be_bytes_32`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.sk.to_be_bytes_32();
+        let result = bytes.to_vec();
+        bytes.zeroize();
+        result
+    }
+}
+
+/// Cheaply check bytes could be a compressed PublicKey, without decoding the point.
+///
+/// Only the length (48 bytes) and the compression flags are checked, so malformed input can be
+/// rejected before the expensive decoding and subgroup check of `PublicKey::from_bytes`, which
+/// is still required. This does not allocate.
+pub fn is_valid_pubkey_bytes(bytes: &[u8]) -> bool {
+    amcl_utils::has_valid_compressed_flags(bytes, amcl_utils::MODBYTES)
 }
 
 #[cfg(test)]
@@ -184,9 +670,9 @@ mod tests {
     extern crate rand;
     extern crate yaml_rust;
 
+    use self::rand::{rngs::StdRng, SeedableRng};
     use self::yaml_rust::yaml;
-    use super::super::amcl_utils::compress_g1;
-    use super::super::signature::Signature;
+    use super::super::amcl_utils::{compress_g1, hash_on_g2};
     use super::*;
     use std::{fs::File, io::prelude::*, path::PathBuf};
 
@@ -276,6 +762,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_public_key_not_in_subgroup() {
+        // (4, y) is on the curve but not in the subgroup
+        let bytes = hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap();
+        assert_eq!(
+            PublicKey::from_bytes(&bytes),
+            Err(DecodeError::NotInSubgroup)
+        );
+
+        let pk = PublicKey::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(pk.as_bytes(), bytes);
+        assert!(!pk.is_in_correct_subgroup());
+
+        assert!(Keypair::random(&mut rand::thread_rng())
+            .pk
+            .is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn test_is_valid_pubkey_bytes() {
+        let mut bytes = Keypair::random(&mut rand::thread_rng()).pk.as_bytes();
+        assert!(is_valid_pubkey_bytes(&bytes));
+        assert!(is_valid_pubkey_bytes(&PublicKey::identity().as_bytes()));
+
+        // Wrong lengths
+        assert!(!is_valid_pubkey_bytes(&[]));
+        assert!(!is_valid_pubkey_bytes(&bytes[1..]));
+        assert!(!is_valid_pubkey_bytes(&[&bytes[..], &[0]].concat()));
+
+        // Infinity with other bits set
+        let mut infinity = PublicKey::identity().as_bytes();
+        infinity[47] = 1;
+        assert!(!is_valid_pubkey_bytes(&infinity));
+        let mut infinity = PublicKey::identity().as_bytes();
+        infinity[0] |= 0x20;
+        assert!(!is_valid_pubkey_bytes(&infinity));
+
+        // c_flag unset
+        bytes[0] &= 0x7f;
+        assert!(!is_valid_pubkey_bytes(&bytes));
+    }
+
+    #[test]
+    fn test_public_key_g1_conversion() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        assert_eq!(*pk.as_g1(), pk.point);
+        let point = pk.clone().into_g1();
+        assert_eq!(point, pk.point);
+        assert_eq!(PublicKey::from_g1(point), pk);
+    }
+
+    #[test]
+    fn test_cached_public_key() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let cached = CachedPublicKey::new(pk.clone());
+        assert_eq!(cached.as_bytes(), pk.as_bytes());
+        assert_eq!(cached.compressed().to_vec(), pk.as_bytes());
+
+        // Clones copy the bytes and the point
+        let clone = cached.clone();
+        assert_eq!(clone.compressed(), cached.compressed());
+        assert_eq!(clone, cached);
+        assert_eq!(*clone.public_key(), pk);
+
+        // Decoding keeps the bytes
+        let decoded = CachedPublicKey::from_bytes(&pk.as_bytes()).unwrap();
+        assert_eq!(decoded.compressed(), cached.compressed());
+        assert_eq!(decoded, cached);
+        assert_eq!(
+            CachedPublicKey::from_bytes(&pk.as_bytes()[1..]),
+            Err(DecodeError::IncorrectSize)
+        );
+
+        // Replacing the PublicKey recomputes the bytes
+        let mut mutated = cached.clone();
+        mutated.set_public_key(pk.neg());
+        assert_eq!(mutated.as_bytes(), pk.neg().as_bytes());
+        assert_ne!(mutated, cached);
+        assert_eq!(cached.as_bytes(), pk.as_bytes());
+        assert_eq!(mutated.into_public_key(), pk.neg());
+
+        // Without interior mutability it can be shared between threads
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<CachedPublicKey>();
+    }
+
+    #[test]
+    fn test_public_key_g1_raw_conversion() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let raw = pk.clone().into_g1_raw();
+        assert_eq!(G1Point::from_raw(raw), pk.point);
+        assert_eq!(PublicKey::from_g1_raw(raw, true), Ok(pk.clone()));
+        assert_eq!(PublicKey::from_g1_raw(raw, false), Ok(pk));
+
+        // (4, y) is on the curve but not in the subgroup
+        let raw = PublicKey::from_bytes_unchecked(&hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap()).unwrap().into_g1_raw();
+        assert_eq!(
+            PublicKey::from_g1_raw(raw, true),
+            Err(DecodeError::NotInSubgroup)
+        );
+        assert!(PublicKey::from_g1_raw(raw, false).is_ok());
+    }
+
+    #[test]
+    fn test_public_key_write_read() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let mut buffer = vec![];
+        pk.write_to(&mut buffer).unwrap();
+        pk.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 96);
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(PublicKey::read_from(&mut reader).unwrap(), pk);
+        assert_eq!(PublicKey::read_from(&mut reader).unwrap(), pk);
+        assert!(PublicKey::read_from(&mut reader).is_err());
+
+        let bad_bytes = vec![0; 48];
+        let err = PublicKey::read_from(&mut bad_bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_public_key_base64() {
+        let pk = PublicKey::generator();
+        let encoded = pk.to_base64();
+        assert_eq!(
+            encoded,
+            "l/HTpzGX15QmlWOMT6msD8NojE+XdLkFoU46PxcbrFhsVeg/+Xoa7/s68ArbIsa7"
+        );
+        assert_eq!(PublicKey::from_base64(&encoded), Ok(pk));
+
+        assert_eq!(
+            PublicKey::from_base64("not base64!"),
+            Err(DecodeError::InvalidBase64)
+        );
+        assert_eq!(
+            PublicKey::from_base64(&encoded[4..]),
+            Err(DecodeError::IncorrectSize)
+        );
+    }
+
+    #[test]
+    fn test_public_key_debug() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let expected = format!("0x{}", hex::encode(pk.as_bytes()));
+        assert_eq!(format!("{:?}", pk), expected);
+    }
+
     #[test]
     fn test_signature_verify_with_serialized_public_key() {
         let sk_bytes = vec![
@@ -307,6 +942,293 @@ mod tests {
         assert!(signature.verify(&message, domain, &pk));
     }
 
+    #[test]
+    fn test_random_seeded() {
+        // Any CSPRNG can be injected, a seeded one gives the same keys every time
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+        let keypair = Keypair::random(&mut rng_a);
+        assert_eq!(keypair, Keypair::random(&mut rng_b));
+        assert_eq!(SecretKey::random(&mut rng_a), SecretKey::random(&mut rng_b));
+        assert_ne!(keypair, Keypair::random(&mut StdRng::seed_from_u64(43)));
+
+        let message = "cats".as_bytes();
+        let signature = Signature::new(&message, 42, &keypair.sk);
+        assert!(Signature::verify_all(
+            &mut rng_a,
+            &[(&keypair.pk, message, 42, &signature)]
+        ));
+    }
+
+    #[test]
+    fn test_secret_key_arithmetic() {
+        // (r - 1) + 2 = 1 and (r - 1) * (r - 1) = 1
+        let mut r_minus_one_bytes = vec![0; MOD_BYTE_SIZE];
+        r_minus_one_bytes[MOD_BYTE_SIZE - 32..].copy_from_slice(
+            &hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000")
+                .unwrap(),
+        );
+        let r_minus_one = SecretKey::from_bytes(&r_minus_one_bytes).unwrap();
+        let mut two_bytes = vec![0; MOD_BYTE_SIZE];
+        two_bytes[MOD_BYTE_SIZE - 1] = 2;
+        let two = SecretKey::from_bytes(&two_bytes).unwrap();
+        let mut one_bytes = vec![0; MOD_BYTE_SIZE];
+        one_bytes[MOD_BYTE_SIZE - 1] = 1;
+        assert_eq!(r_minus_one.add(&two).as_bytes(), one_bytes);
+        assert_eq!(r_minus_one.mul(&r_minus_one).as_bytes(), one_bytes);
+
+        // Derived PublicKeys are consistent with the group operations
+        let a = SecretKey::random(&mut rand::thread_rng());
+        let b = SecretKey::random(&mut rand::thread_rng());
+        let c = SecretKey::random(&mut rand::thread_rng());
+        let pk_a = PublicKey::from_secret_key(&a);
+        let pk_b = PublicKey::from_secret_key(&b);
+        let pk_c = PublicKey::from_secret_key(&c);
+
+        let mut sum = pk_a.point.clone();
+        sum.add(&pk_b.point);
+        sum.add(&pk_c.point);
+        let from_sum = PublicKey::from_secret_keys_sum(&[&a, &b, &c]);
+        assert_eq!(from_sum.point, sum);
+        assert_eq!(from_sum, PublicKey::from_secret_key(&a.add(&b).add(&c)));
+
+        let product = PublicKey::new_from_raw(&pk_a.point.as_raw().mul(&b.x));
+        assert_eq!(PublicKey::from_secret_key(&a.mul(&b)), product);
+    }
+
+    #[test]
+    fn test_key_validate() {
+        assert_eq!(
+            Keypair::random(&mut rand::thread_rng()).pk.key_validate(),
+            Ok(())
+        );
+        assert_eq!(
+            PublicKey::new_from_raw(&GroupG1::new()).key_validate(),
+            Err(DecodeError::PointAtInfinityDisallowed)
+        );
+    }
+
+    #[test]
+    fn test_batch_from_bytes() {
+        let keys: Vec<PublicKey> = (0..5)
+            .map(|_| Keypair::random(&mut rand::thread_rng()).pk)
+            .collect();
+        let blobs: Vec<Vec<u8>> = keys.iter().map(|key| key.as_bytes()).collect();
+        let blob_refs: Vec<&[u8]> = blobs.iter().map(|blob| blob.as_slice()).collect();
+        assert_eq!(PublicKey::batch_from_bytes(&blob_refs), Ok(keys));
+        assert_eq!(PublicKey::batch_from_bytes(&[]), Ok(vec![]));
+
+        // (4, y) is on the curve but not in the subgroup
+        let not_in_subgroup = hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap();
+        let short = &blobs[0][..47];
+
+        assert_eq!(
+            PublicKey::batch_from_bytes(&[blob_refs[0], short, blob_refs[1]]),
+            Err((1, DecodeError::IncorrectSize))
+        );
+        assert_eq!(
+            PublicKey::batch_from_bytes(&[blob_refs[0], blob_refs[1], &not_in_subgroup]),
+            Err((2, DecodeError::NotInSubgroup))
+        );
+
+        // The first failure is reported whichever check finds it
+        assert_eq!(
+            PublicKey::batch_from_bytes(&[blob_refs[0], &not_in_subgroup, short]),
+            Err((1, DecodeError::NotInSubgroup))
+        );
+        assert_eq!(
+            PublicKey::batch_from_bytes(&[blob_refs[0], short, &not_in_subgroup]),
+            Err((1, DecodeError::IncorrectSize))
+        );
+    }
+
+    #[test]
+    fn test_batch_key_validate() {
+        let valid: Vec<PublicKey> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()).pk)
+            .collect();
+        let infinity = PublicKey::new_from_raw(&GroupG1::new());
+        // (4, y) is on the curve but not in the subgroup
+        let not_in_subgroup = PublicKey::from_bytes_unchecked(&hex::decode("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004").unwrap()).unwrap();
+
+        assert_eq!(
+            PublicKey::batch_key_validate(&[&valid[0], &valid[1], &valid[2]]),
+            Ok(())
+        );
+        assert_eq!(PublicKey::batch_key_validate(&[]), Ok(()));
+        assert_eq!(
+            PublicKey::batch_key_validate(&[&valid[0], &infinity, &valid[1], &not_in_subgroup]),
+            Err(vec![1, 3])
+        );
+    }
+
+    #[test]
+    fn test_keypair_serialization_isomorphism() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let bytes = keypair.to_bytes();
+        assert_eq!(bytes, keypair.sk.to_be_bytes_32().to_vec());
+        assert_eq!(Keypair::from_bytes(&bytes).unwrap(), keypair);
+        assert_eq!(
+            Keypair::from_bytes(&bytes[1..]),
+            Err(DecodeError::IncorrectSize)
+        );
+        assert_eq!(
+            Keypair::from_bytes(&keypair.sk.as_bytes()),
+            Err(DecodeError::IncorrectSize)
+        );
+    }
+
+    #[test]
+    fn test_public_key_neg() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let mut sum = pk.point.clone();
+        sum.add(&pk.neg().point);
+        assert!(sum.is_infinity());
+        assert_eq!(pk.neg().neg(), pk);
+        assert_eq!(
+            PublicKey::generator().neg(),
+            PublicKey::negative_generator()
+        );
+
+        let infinity = PublicKey::new_from_raw(&GroupG1::new());
+        assert_eq!(infinity.neg(), infinity);
+    }
+
+    #[test]
+    fn test_generators() {
+        let mut one_bytes = vec![0; MOD_BYTE_SIZE];
+        one_bytes[MOD_BYTE_SIZE - 1] = 1;
+        let one = SecretKey::from_bytes(&one_bytes).unwrap();
+        assert_eq!(PublicKey::generator(), PublicKey::from_secret_key(&one));
+        assert_eq!(
+            PublicKey::generator().as_bytes(),
+            hex::decode("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb").unwrap()
+        );
+        assert_eq!(
+            Signature::generator().as_bytes(),
+            hex::decode("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8").unwrap()
+        );
+
+        let mut sum = PublicKey::generator().point;
+        sum.add(&PublicKey::negative_generator().point);
+        assert!(sum.is_infinity());
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_keypair_generate() {
+        let keypair = Keypair::generate();
+        assert_eq!(keypair.pk, PublicKey::from_secret_key(&keypair.sk));
+        assert_ne!(Keypair::generate(), keypair);
+
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+        assert!(signature.verify(b"cats", 42, &keypair.pk));
+    }
+
+    #[test]
+    fn test_secret_key_be_bytes_32() {
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        let bytes = sk.to_be_bytes_32();
+        assert_eq!(bytes[..], sk.as_bytes()[16..]);
+        assert_eq!(SecretKey::from_be_bytes_32(&bytes), Ok(sk));
+
+        // The 32 byte form is the 48 byte form without the leading zeros
+        let bytes = hex::decode("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3")
+            .unwrap();
+        let sk = SecretKey::from_be_bytes_32(&bytes).unwrap();
+        assert_eq!(
+            SecretKey::from_bytes(&[&[0; 16], &bytes[..]].concat()),
+            Ok(sk.clone())
+        );
+        assert_eq!(sk.to_be_bytes_32()[..], bytes[..]);
+
+        assert_eq!(
+            SecretKey::from_be_bytes_32(&bytes[1..]),
+            Err(DecodeError::IncorrectSize)
+        );
+        assert_eq!(
+            SecretKey::from_be_bytes_32(&sk.as_bytes()),
+            Err(DecodeError::IncorrectSize)
+        );
+
+        // The curve order does not fit
+        let order = hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001")
+            .unwrap();
+        assert_eq!(
+            SecretKey::from_be_bytes_32(&order),
+            Err(DecodeError::NonCanonicalEncoding)
+        );
+        assert_eq!(
+            SecretKey::from_be_bytes_32(&[0xff; 32]),
+            Err(DecodeError::NonCanonicalEncoding)
+        );
+    }
+
+    #[test]
+    fn test_secret_key_checksummed_bytes() {
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        let mut bytes = sk.to_checksummed_bytes();
+        assert_eq!(bytes.len(), 36);
+        assert_eq!(bytes[..32], sk.to_be_bytes_32()[..]);
+        assert_eq!(SecretKey::from_checksummed_bytes(&bytes), Ok(sk));
+
+        assert_eq!(
+            SecretKey::from_checksummed_bytes(&bytes[..32]),
+            Err(DecodeError::IncorrectSize)
+        );
+
+        // The checksum is the first 4 bytes of SHA256 over the 32 byte key
+        let sk_bytes =
+            hex::decode("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3")
+                .unwrap();
+        let sk = SecretKey::from_be_bytes_32(&sk_bytes).unwrap();
+        assert_eq!(
+            sk.to_checksummed_bytes()[..],
+            [&sk_bytes[..], &hex::decode("afe10a82").unwrap()[..]].concat()[..]
+        );
+
+        // A single transcription error is caught
+        bytes[20] ^= 1;
+        assert_eq!(
+            SecretKey::from_checksummed_bytes(&bytes),
+            Err(DecodeError::InvalidChecksum)
+        );
+        bytes[20] ^= 1;
+        bytes[34] ^= 1;
+        assert_eq!(
+            SecretKey::from_checksummed_bytes(&bytes),
+            Err(DecodeError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_secret_key_scalar() {
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        let scalar = sk.as_scalar();
+        assert_eq!(SecretKey::from_scalar(&scalar).unwrap(), sk);
+
+        let order = BigNum::new_ints(&CURVE_ORDER);
+        assert_eq!(
+            SecretKey::from_scalar(&order),
+            Err(DecodeError::NonCanonicalEncoding)
+        );
+        let mut r_minus_one = BigNum::new_copy(&order);
+        r_minus_one.dec(1);
+        assert!(SecretKey::from_scalar(&r_minus_one).is_ok());
+    }
+
+    #[test]
+    fn test_sign_prehashed() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let message = "cats".as_bytes();
+        let domain = 42;
+
+        let hash_point = G2Point::from_raw(hash_on_g2(&message, domain));
+        let signature = keypair.sk.sign_prehashed(&hash_point);
+        assert!(signature.verify(&message, domain, &keypair.pk));
+        assert_eq!(signature, Signature::new(&message, domain, &keypair.sk));
+    }
+
     // Test vector from https://github.com/ethereum/eth2.0-tests/blob/master/bls/test_bls.yml
     // case03_private_to_public_key
     #[test]
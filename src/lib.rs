@@ -11,7 +11,7 @@
 extern crate alloc;
 
 #[cfg(not(feature = "std"))]
-pub(crate) mod prelude {
+pub(crate) mod alloc_prelude {
     pub use alloc::prelude::v1::*;
     pub use core::prelude::v1::*;
 }
@@ -19,29 +19,63 @@ pub(crate) mod prelude {
 #[cfg(not(feature = "std"))]
 #[allow(unused)]
 #[prelude_import]
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 extern crate amcl;
 #[cfg(feature = "std")]
 #[macro_use]
 extern crate lazy_static;
 extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "ct")]
+extern crate subtle;
 
 mod aggregates;
 mod amcl_utils;
+pub mod constants;
 mod errors;
+mod eth2;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 mod g1;
 mod g2;
+mod gt;
 mod keys;
+mod pop;
+pub mod prelude;
 mod rng;
+mod scheme;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde_bytes;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde_hex;
 mod signature;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
 
 use self::amcl::bls381 as BLSCurve;
 
-pub use aggregates::{AggregatePublicKey, AggregateSignature};
-pub use amcl_utils::{compress_g2, hash_on_g2};
-pub use errors::DecodeError;
-pub use g1::G1Point;
-pub use g2::G2Point;
-pub use keys::{Keypair, PublicKey, SecretKey};
-pub use signature::Signature;
+pub use aggregates::{
+    verify_aggregate, verify_committee, AggregateProof, AggregatePublicKey, AggregateSignature,
+    AggregateVerifier, CommitteeProof, CountedAggregateSignature, PreparedAggregatePublicKey,
+    SignatureAccumulator, SignatureSet,
+};
+pub use amcl_utils::{
+    clear_cofactor_g1, clear_cofactor_g2, compress_g2, domain_to_dst, expand_message_xmd,
+    hash_on_g1, hash_on_g2, hash_on_g2_no_clear, hash_on_g2_with_domain_bytes, hash_to_curve_g1,
+    hash_to_curve_g2, hash_to_field_fp, hash_to_field_fp2, is_in_g1_subgroup, is_in_g2_subgroup,
+    map_to_curve_g1, map_to_curve_g2,
+};
+pub use errors::{DecodeError, SignError, VerifyError};
+pub use eth2::{Eth2Domain, ETH2_DST};
+pub use g1::{G1Point, G1Wrapper};
+pub use g2::{hash_many_on_g2, G2Point};
+pub use gt::{check_pairing_product, verification_witness, Gt};
+pub use keys::{is_valid_pubkey_bytes, CachedPublicKey, Keypair, PublicKey, SecretKey};
+pub use pop::{pop_prove, pop_verify, POP_DST};
+pub use scheme::{min_pk, min_sig, MinPubkey, MinSignature, Scheme};
+pub use signature::{
+    deserialize_signatures, is_valid_signature_bytes, serialize_signatures, verify_bytes,
+    PartialSignature, Signature, SignatureReader, Signer, SignerConfig,
+};
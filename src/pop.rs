@@ -0,0 +1,91 @@
+use super::amcl_utils::{self, ate2_evaluation, hash_to_curve_g2};
+use super::g2::G2Point;
+use super::keys::{PublicKey, SecretKey};
+use super::signature::Signature;
+
+/// The domain separation tag for proofs of possession in the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite.
+///
+/// This is distinct from the signing tag so a proof can never be used as a signature.
+pub const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Prove possession of a SecretKey, this is `PopProve` from the IRTF BLS signature draft.
+///
+/// The proof is the SecretKey multiplied by the hash of the 48 byte compressed PublicKey,
+/// hashed with `POP_DST`.
+pub fn pop_prove(sk: &SecretKey) -> Signature {
+    let pk = PublicKey::from_secret_key(sk);
+    let mut point = hash_to_curve_g2(&pk.as_bytes(), POP_DST).mul(&sk.x);
+    point.affine();
+    Signature {
+        point: G2Point::from_raw(point),
+    }
+}
+
+/// Verify a proof of possession, this is `PopVerify` from the IRTF BLS signature draft.
+///
+/// Returns false if the PublicKey is infinity or either point is outside its subgroup.
+pub fn pop_verify(pk: &PublicKey, proof: &Signature) -> bool {
+    if pk.point.is_infinity() || !pk.is_in_correct_subgroup() || !proof.is_in_correct_subgroup() {
+        return false;
+    }
+    let msg_hash_point = hash_to_curve_g2(&pk.as_bytes(), POP_DST);
+
+    // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
+    let generator_g1_negative = amcl_utils::generator_g1_negative();
+    ate2_evaluation(
+        &proof.point.as_raw(),
+        &generator_g1_negative,
+        &msg_hash_point,
+        &pk.point.as_raw(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+    extern crate rand;
+
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_pop_known_answer() {
+        // The key and PublicKey of the consensus-spec bls/sign test vectors. There are no
+        // published PopProve vectors for this ciphersuite, the proof is from an independent
+        // Python implementation of the IRTF draft which reproduces those sign vectors.
+        let sk = SecretKey::from_be_bytes_32(
+            &hex::decode("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3")
+                .unwrap(),
+        )
+        .unwrap();
+        let pk = PublicKey::from_secret_key(&sk);
+        assert_eq!(pk.as_bytes(), hex::decode("a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a").unwrap());
+
+        let proof = pop_prove(&sk);
+        assert_eq!(proof.as_bytes(), hex::decode("b803eb0ed93ea10224a73b6b9c725796be9f5fefd215ef7a5b97234cc956cf6870db6127b7e4d824ec62276078e787db05584ce1adbf076bc0808ca0f15b73d59060254b25393d95dfc7abe3cda566842aaedf50bbb062aae1bbb6ef3b1f77e1").unwrap());
+        assert!(pop_verify(&pk, &proof));
+    }
+
+    #[test]
+    fn test_pop_verify() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let proof = pop_prove(&keypair.sk);
+        assert!(pop_verify(&keypair.pk, &proof));
+
+        // Wrong key
+        let other = Keypair::random(&mut rand::thread_rng());
+        assert!(!pop_verify(&other.pk, &proof));
+
+        // A signature over the PublicKey bytes is not a proof of possession
+        let sig = Signature::new(&keypair.pk.as_bytes(), 0, &keypair.sk);
+        assert!(!pop_verify(&keypair.pk, &sig));
+
+        // Infinity
+        let infinity = PublicKey::new_from_raw(&amcl_utils::GroupG1::new());
+        let infinity_proof = Signature {
+            point: G2Point::new(),
+        };
+        assert!(!pop_verify(&infinity, &infinity_proof));
+    }
+}
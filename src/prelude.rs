@@ -0,0 +1,45 @@
+//! The high-level API in one import.
+//!
+//! `use milagro_bls::prelude::*;` brings in the key and signature types, their aggregates and
+//! the functions to verify them.
+
+pub use aggregates::{verify_aggregate, verify_committee, AggregatePublicKey, AggregateSignature};
+pub use errors::{DecodeError, VerifyError};
+pub use keys::{Keypair, PublicKey, SecretKey};
+pub use pop::{pop_prove, pop_verify};
+pub use signature::Signature;
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        let domain = 45;
+        let msg = [42; 32];
+        let keypairs: Vec<Keypair> = (0..3)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+
+        let sigs: Vec<Signature> = keypairs
+            .iter()
+            .map(|kp| Signature::new(&msg, domain, &kp.sk))
+            .collect();
+        let sig_refs: Vec<&Signature> = sigs.iter().collect();
+        let pk_refs: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        assert!(verify_aggregate(&sig_refs, &pk_refs, &msg, domain));
+
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.extend(sigs.iter());
+        assert!(agg_sig.verify(
+            &msg,
+            domain,
+            &AggregatePublicKey::from_public_keys(&pk_refs)
+        ));
+
+        let pop = pop_prove(&keypairs[0].sk);
+        assert!(pop_verify(&keypairs[0].pk, &pop));
+    }
+}
@@ -1,11 +1,17 @@
 extern crate amcl;
+#[cfg(feature = "getrandom")]
+extern crate getrandom;
 extern crate rand;
+#[cfg(feature = "getrandom")]
+extern crate zeroize;
 
-use rand::Rng;
+#[cfg(feature = "getrandom")]
+use self::zeroize::Zeroize;
+use rand::{CryptoRng, RngCore};
 
 use self::amcl::rand::RAND;
 
-pub fn get_seeded_rng<R: Rng + ?Sized>(rng: &mut R, entropy_size: usize) -> RAND {
+pub fn get_seeded_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R, entropy_size: usize) -> RAND {
     // Generate entropy to seed the RNG
     let mut entropy = vec![0; entropy_size];
     rng.fill_bytes(&mut entropy.as_mut_slice());
@@ -16,3 +22,18 @@ pub fn get_seeded_rng<R: Rng + ?Sized>(rng: &mut R, entropy_size: usize) -> RAND
     rng.seed(entropy_size, &entropy);
     rng
 }
+
+// Seed the amcl RNG directly from the operating system entropy source.
+//
+// Panics if `getrandom` fails, which only happens if the platform has no entropy source.
+#[cfg(feature = "getrandom")]
+pub fn get_getrandom_seeded_rng(entropy_size: usize) -> RAND {
+    let mut entropy = vec![0; entropy_size];
+    getrandom::getrandom(&mut entropy).expect("getrandom failed to provide entropy");
+
+    let mut rng = RAND::new();
+    rng.clean();
+    rng.seed(entropy_size, &entropy);
+    entropy.zeroize();
+    rng
+}
@@ -0,0 +1,96 @@
+use super::g1::G1Point;
+use super::g2::G2Point;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// The assignment of PublicKeys and Signatures to the groups G1 and G2.
+///
+/// `PublicKey` and `Signature` are generic over the Scheme so verifying a Signature against a
+/// PublicKey of another Scheme is a type error rather than a failed pairing check. The trait is
+/// sealed, `MinPubkey` and `MinSignature` are the only Schemes.
+pub trait Scheme: private::Sealed {
+    /// The point type of a PublicKey.
+    type PublicKeyPoint;
+    /// The point type of a Signature.
+    type SignaturePoint;
+}
+
+/// PublicKeys in G1 and Signatures in G2, the default and the Scheme used by eth2.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MinPubkey;
+
+impl private::Sealed for MinPubkey {}
+
+impl Scheme for MinPubkey {
+    type PublicKeyPoint = G1Point;
+    type SignaturePoint = G2Point;
+}
+
+/// PublicKeys in G2 and Signatures in G1.
+///
+/// There is no implementation of this Scheme yet, the types can be named but no keys or
+/// Signatures are provided for them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MinSignature;
+
+impl private::Sealed for MinSignature {}
+
+impl Scheme for MinSignature {
+    type PublicKeyPoint = G2Point;
+    type SignaturePoint = G1Point;
+}
+
+/// The types of the `MinPubkey` Scheme.
+pub mod min_pk {
+    use super::MinPubkey;
+
+    pub type PublicKey = super::super::keys::PublicKey<MinPubkey>;
+    pub type Signature = super::super::signature::Signature<MinPubkey>;
+}
+
+/// The types of the `MinSignature` Scheme.
+pub mod min_sig {
+    use super::MinSignature;
+
+    pub type PublicKey = super::super::keys::PublicKey<MinSignature>;
+    pub type Signature = super::super::signature::Signature<MinSignature>;
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::keys::{Keypair, PublicKey};
+    use super::super::signature::Signature;
+    use super::*;
+
+    #[test]
+    fn test_min_pk_is_default() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+
+        let pk: min_pk::PublicKey = keypair.pk.clone();
+        let sig: min_pk::Signature = signature.clone();
+        assert!(sig.verify(b"cats", 42, &pk));
+
+        let pk: PublicKey = pk;
+        let sig: Signature = sig;
+        assert_eq!(pk, keypair.pk);
+        assert_eq!(sig, signature);
+    }
+
+    #[test]
+    fn test_min_sig_points() {
+        // The groups are swapped, the types can be built from points directly
+        let pk = min_sig::PublicKey {
+            point: G2Point::new(),
+        };
+        let sig = min_sig::Signature {
+            point: G1Point::new(),
+        };
+        assert!(pk.point.is_infinity());
+        assert!(sig.point.is_infinity());
+    }
+}
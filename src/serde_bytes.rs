@@ -0,0 +1,245 @@
+//! Serialize and deserialize as raw compressed bytes, for use with
+//! `#[serde(with = "milagro_bls::serde_bytes")]`.
+//!
+//! This is also the default `Serialize` and `Deserialize` implementation of the key and
+//! signature types.
+
+extern crate serde;
+extern crate zeroize;
+
+use self::serde::de::{Error, SeqAccess, Visitor};
+use self::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use self::zeroize::Zeroize;
+use super::aggregates::{AggregatePublicKey, AggregateSignature};
+use super::errors::DecodeError;
+use super::keys::{Keypair, PublicKey};
+use super::signature::Signature;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A type with a canonical compressed byte encoding, used by `serde_bytes` and `serde_hex`.
+pub trait ByteEncoding: Sized {
+    fn to_encoded_bytes(&self) -> Vec<u8>;
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError>;
+}
+
+impl ByteEncoding for PublicKey {
+    fn to_encoded_bytes(&self) -> Vec<u8> {
+        self.as_bytes()
+    }
+
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        PublicKey::from_bytes(bytes)
+    }
+}
+
+impl ByteEncoding for Signature {
+    fn to_encoded_bytes(&self) -> Vec<u8> {
+        self.as_bytes()
+    }
+
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Signature::from_bytes(bytes)
+    }
+}
+
+impl ByteEncoding for AggregatePublicKey {
+    fn to_encoded_bytes(&self) -> Vec<u8> {
+        self.as_bytes()
+    }
+
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        AggregatePublicKey::from_bytes(bytes)
+    }
+}
+
+impl ByteEncoding for AggregateSignature {
+    fn to_encoded_bytes(&self) -> Vec<u8> {
+        self.as_bytes()
+    }
+
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        AggregateSignature::from_bytes(bytes)
+    }
+}
+
+impl ByteEncoding for Keypair {
+    fn to_encoded_bytes(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    fn from_encoded_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Keypair::from_bytes(bytes)
+    }
+}
+
+// Serialize as a byte string of the compressed bytes, e.g. a CBOR byte string rather than an
+// array of integers.
+macro_rules! impl_serde_as_bytes {
+    ($name: ident) => {
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize(self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize(deserializer)
+            }
+        }
+    };
+}
+
+impl_serde_as_bytes!(PublicKey);
+impl_serde_as_bytes!(Signature);
+impl_serde_as_bytes!(AggregatePublicKey);
+impl_serde_as_bytes!(AggregateSignature);
+
+/// A Keypair is serialized as the 32 big-endian SecretKey bytes, the PublicKey is derived on
+/// deserialization.
+impl Serialize for Keypair {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = self.to_bytes();
+        let result = serializer.serialize_bytes(&bytes);
+        bytes.zeroize();
+        result
+    }
+}
+
+impl<'de> Deserialize<'de> for Keypair {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+/// Serialize the compressed bytes.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ByteEncoding,
+    S: Serializer,
+{
+    serializer.serialize_bytes(&value.to_encoded_bytes())
+}
+
+/// Deserialize from compressed bytes, accepting either a byte string or a sequence of bytes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: ByteEncoding,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+}
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: ByteEncoding> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "compressed point bytes")
+    }
+
+    fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<T, E> {
+        T::from_encoded_bytes(bytes).map_err(|e| E::custom(format!("{:?}", e)))
+    }
+
+    // Owned buffers may hold SecretKey bytes so are zeroized once decoded.
+    fn visit_byte_buf<E: Error>(self, mut bytes: Vec<u8>) -> Result<T, E> {
+        let result = self.visit_bytes(&bytes);
+        bytes.zeroize();
+        result
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = vec![];
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ciborium;
+    extern crate rand;
+    extern crate serde_json;
+
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_serde_bytes_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+
+        let mut json = vec![];
+        serialize(&keypair.pk, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        let decoded: PublicKey =
+            deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(decoded, keypair.pk);
+
+        let mut json = vec![];
+        serialize(&signature, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        let decoded: Signature =
+            deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(decoded, signature);
+    }
+
+    #[test]
+    fn test_keypair_serde() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let json = serde_json::to_string(&keypair).unwrap();
+        assert_eq!(
+            json,
+            serde_json::to_string(&keypair.sk.to_be_bytes_32()).unwrap()
+        );
+        let decoded: Keypair = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, keypair);
+    }
+
+    #[test]
+    fn test_cbor_byte_strings() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+        let mut agg_pub = AggregatePublicKey::new();
+        agg_pub.add(&keypair.pk);
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.add(&signature);
+
+        // A byte string has major type 2, 0x58 is followed by a one byte length
+        let mut cbor = vec![];
+        ciborium::ser::into_writer(&keypair.pk, &mut cbor).unwrap();
+        assert_eq!(cbor[..2], [0x58, 48]);
+        assert_eq!(cbor[2..], keypair.pk.as_bytes()[..]);
+        let decoded: PublicKey = ciborium::de::from_reader(&cbor[..]).unwrap();
+        assert_eq!(decoded, keypair.pk);
+
+        let mut cbor = vec![];
+        ciborium::ser::into_writer(&signature, &mut cbor).unwrap();
+        assert_eq!(cbor[..2], [0x58, 96]);
+        assert_eq!(cbor.len(), 98);
+        let decoded: Signature = ciborium::de::from_reader(&cbor[..]).unwrap();
+        assert_eq!(decoded, signature);
+
+        let mut cbor = vec![];
+        ciborium::ser::into_writer(&agg_pub, &mut cbor).unwrap();
+        assert_eq!(cbor[..2], [0x58, 48]);
+        let decoded: AggregatePublicKey = ciborium::de::from_reader(&cbor[..]).unwrap();
+        assert_eq!(decoded, agg_pub);
+
+        let mut cbor = vec![];
+        ciborium::ser::into_writer(&agg_sig, &mut cbor).unwrap();
+        assert_eq!(cbor[..2], [0x58, 96]);
+        let decoded: AggregateSignature = ciborium::de::from_reader(&cbor[..]).unwrap();
+        assert_eq!(decoded, agg_sig);
+    }
+
+    #[test]
+    fn test_serde_bytes_incorrect_size() {
+        let result: Result<PublicKey, _> =
+            deserialize(&mut serde_json::Deserializer::from_str("[1, 2, 3]"));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Serialize and deserialize as a `0x` prefixed hex string of the compressed bytes, for use
+//! with `#[serde(with = "milagro_bls::serde_hex")]`.
+
+extern crate hex;
+extern crate serde;
+
+use self::serde::de::{Error, Visitor};
+use self::serde::{Deserializer, Serializer};
+pub use super::serde_bytes::ByteEncoding;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Serialize the compressed bytes as a `0x` prefixed hex string.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ByteEncoding,
+    S: Serializer,
+{
+    serializer.serialize_str(&format!("0x{}", hex::encode(value.to_encoded_bytes())))
+}
+
+/// Deserialize from a hex string of the compressed bytes, the `0x` prefix is optional.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: ByteEncoding,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(HexVisitor(PhantomData))
+}
+
+struct HexVisitor<T>(PhantomData<T>);
+
+impl<'de, T: ByteEncoding> Visitor<'de> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a hex string of compressed point bytes")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<T, E> {
+        let value = value.trim_start_matches("0x");
+        let bytes = hex::decode(value).map_err(|e| E::custom(format!("{:?}", e)))?;
+        T::from_encoded_bytes(&bytes).map_err(|e| E::custom(format!("{:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+    extern crate serde_json;
+
+    use super::super::keys::{Keypair, PublicKey};
+    use super::super::signature::Signature;
+    use super::*;
+
+    #[test]
+    fn test_serde_hex_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+
+        let mut json = vec![];
+        serialize(&keypair.pk, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(
+            json,
+            format!("\"0x{}\"", hex::encode(keypair.pk.as_bytes())).into_bytes()
+        );
+        let decoded: PublicKey =
+            deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(decoded, keypair.pk);
+
+        let mut json = vec![];
+        serialize(&signature, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        let decoded: Signature =
+            deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(decoded, signature);
+    }
+
+    #[test]
+    fn test_serde_hex_invalid() {
+        let result: Result<PublicKey, _> =
+            deserialize(&mut serde_json::Deserializer::from_str("\"0xzz\""));
+        assert!(result.is_err());
+        let result: Result<PublicKey, _> =
+            deserialize(&mut serde_json::Deserializer::from_str("\"0x0102\""));
+        assert!(result.is_err());
+    }
+}
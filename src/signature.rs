@@ -1,14 +1,30 @@
 extern crate amcl;
+#[cfg(feature = "base64")]
+extern crate base64;
 
-use super::amcl_utils::{self, ate2_evaluation, ate_pairing, hash_on_g2, map_to_g2};
-use super::errors::DecodeError;
+use super::aggregates::AggregateSignature;
+use super::amcl_utils::{
+    self, ate2_evaluation, ate_pairing, hash_on_g2, hash_on_g2_with_domain_bytes, hash_to_curve_g2,
+    map_to_g2, BigNum,
+};
+use super::errors::{DecodeError, SignError};
+use super::eth2::{Eth2Domain, ETH2_DST};
 use super::g2::G2Point;
 use super::keys::{PublicKey, SecretKey};
+use super::scheme::{MinPubkey, Scheme};
+#[cfg(not(feature = "std"))]
+use core::fmt;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use std::{fmt, io};
 
+/// A BLS Signature.
+///
+/// The Scheme defaults to `MinPubkey`, Signatures in G2. A Signature only verifies against a
+/// PublicKey of the same Scheme.
 #[derive(Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
-pub struct Signature {
-    pub point: G2Point,
+pub struct Signature<S: Scheme = MinPubkey> {
+    pub point: S::SignaturePoint,
 }
 
 impl Signature {
@@ -22,6 +38,64 @@ impl Signature {
         }
     }
 
+    /// The Signature of the G2 generator.
+    pub fn generator() -> Self {
+        Self {
+            point: {
+                #[cfg(feature = "std")]
+                {
+                    G2Point::from_raw(*amcl_utils::GENERATORG2)
+                }
+                #[cfg(not(feature = "std"))]
+                {
+                    G2Point::from_raw(amcl_utils::GroupG2::generator())
+                }
+            },
+        }
+    }
+
+    /// The Signature of the point at infinity, the identity of G2.
+    pub fn identity() -> Self {
+        Self {
+            point: G2Point::new(),
+        }
+    }
+
+    /// Instantiate a Signature from a G2Point.
+    ///
+    /// The point is not checked, see `is_in_correct_subgroup`.
+    pub fn from_g2(point: G2Point) -> Self {
+        Self { point }
+    }
+
+    /// Instantiate a Signature from a raw `amcl` G2 point.
+    ///
+    /// If `check_subgroup` is true the point must be in the G2 subgroup, otherwise it is not
+    /// checked, see `is_in_correct_subgroup`.
+    pub fn from_g2_raw(point: GroupG2, check_subgroup: bool) -> Result<Self, DecodeError> {
+        if check_subgroup && !amcl_utils::is_in_g2_subgroup(&point) {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(Self {
+            point: G2Point::from_raw(point),
+        })
+    }
+
+    /// Convert the Signature into its raw `amcl` G2 point.
+    pub fn into_g2_raw(self) -> GroupG2 {
+        self.point.into_raw()
+    }
+
+    /// The G2Point of the Signature.
+    pub fn as_g2(&self) -> &G2Point {
+        &self.point
+    }
+
+    /// Convert the Signature into its G2Point.
+    pub fn into_g2(self) -> G2Point {
+        self.point
+    }
+
     /// Instantiate a new Signature from a message and a SecretKey, where the message has already
     /// been hashed.
     pub fn new_hashed(msg_hash_real: &[u8], msg_hash_imaginary: &[u8], sk: &SecretKey) -> Self {
@@ -37,13 +111,19 @@ impl Signature {
     ///
     /// In theory, should only return true if the PublicKey matches the SecretKey used to
     /// instantiate the Signature.
+    ///
+    /// Returns false if the PublicKey is the identity, which fails `KeyValidate` in the IRTF
+    /// BLS signature draft. Otherwise the identity Signature would verify against it for any
+    /// message.
     pub fn verify(&self, msg: &[u8], d: u64, pk: &PublicKey) -> bool {
+        if pk.point.is_infinity() {
+            return false;
+        }
         let mut msg_hash_point = hash_on_g2(msg, d);
         msg_hash_point.affine();
 
         // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
-        let mut generator_g1_negative = amcl_utils::GroupG1::generator();
-        generator_g1_negative.neg();
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
         ate2_evaluation(
             &self.point.as_raw(),
             &generator_g1_negative,
@@ -52,18 +132,123 @@ impl Signature {
         )
     }
 
+    /// Instantiate a new Signature from a message and a SecretKey, with the domain as an
+    /// arbitrary byte string.
+    ///
+    /// A `u64` domain `d` is the same as `&d.to_be_bytes()`, so this is identical to `new` for
+    /// those domains. An application should always use domains of the same length, see
+    /// `hash_on_g2_with_domain_bytes`.
+    pub fn sign_with_domain_bytes(msg: &[u8], domain: &[u8], sk: &SecretKey) -> Self {
+        let hash_point = hash_on_g2_with_domain_bytes(msg, domain);
+        let mut sig = hash_point.mul(&sk.x);
+        sig.affine();
+        Self {
+            point: G2Point::from_raw(sig),
+        }
+    }
+
+    /// Verify the Signature against a PublicKey, with the domain as an arbitrary byte string.
+    ///
+    /// This is the verifying counterpart of `sign_with_domain_bytes`, and is identical to
+    /// `verify` for a `u64` domain `d` given as `&d.to_be_bytes()`.
+    pub fn verify_with_domain_bytes(&self, msg: &[u8], domain: &[u8], pk: &PublicKey) -> bool {
+        if pk.point.is_infinity() {
+            return false;
+        }
+        let mut msg_hash_point = hash_on_g2_with_domain_bytes(msg, domain);
+        msg_hash_point.affine();
+
+        // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
+        ate2_evaluation(
+            self.point.as_raw(),
+            &generator_g1_negative,
+            &msg_hash_point,
+            pk.point.as_raw(),
+        )
+    }
+
+    /// Verify the Signature against a PublicKey, this is identical to `verify`.
+    ///
+    /// `verify` already works on borrowed points without cloning them, this name is kept so
+    /// existing callers of `verify_ref` continue to compile.
+    pub fn verify_ref(&self, msg: &[u8], d: u64, pk: &PublicKey) -> bool {
+        self.verify(msg, d, pk)
+    }
+
+    /// Rerandomize the Signature and its PublicKey by a blinding factor `r`.
+    ///
+    /// Returns `(r * S, r * PK)`. As `e(r * S, G1) = e(H(m), PK)^r = e(H(m), r * PK)` the new
+    /// pair verifies the same message, but cannot be linked to the original without `r`.
+    ///
+    /// The blinding factor must be random and non-zero modulo the curve order, a zero factor
+    /// gives infinity for both points.
+    pub fn rerandomize(&self, pk: &PublicKey, blinding: &BigNum) -> (Signature, PublicKey) {
+        let mut sig_point = self.point.as_raw().mul(blinding);
+        sig_point.affine();
+        let mut pk_point = pk.point.as_raw().mul(blinding);
+        pk_point.affine();
+        (
+            Signature {
+                point: G2Point::from_raw(sig_point),
+            },
+            PublicKey::new_from_raw(&pk_point),
+        )
+    }
+
+    /// Verify the Signature against a PublicKey and a 32 byte eth2 signing root.
+    pub fn verify_signing_root(&self, root: &[u8; 32], d: u64, pk: &PublicKey) -> bool {
+        self.verify(root, d, pk)
+    }
+
+    /// Sign a 32 byte eth2 object root in a 32 byte eth2 domain.
+    ///
+    /// The signing root of `root` in `domain` is hashed to G2 with `ETH2_DST`, as eth2 validator
+    /// clients do. See `Eth2Domain::signing_root`.
+    pub fn new_eth2(root: &[u8; 32], domain: &Eth2Domain, sk: &SecretKey) -> Self {
+        let hash_point = hash_to_curve_g2(&domain.signing_root(root), ETH2_DST);
+        let mut sig = hash_point.mul(&sk.x);
+        sig.affine();
+        Self {
+            point: G2Point::from_raw(sig),
+        }
+    }
+
+    /// Verify the Signature against a PublicKey, a 32 byte eth2 object root and a 32 byte eth2
+    /// domain, see `new_eth2`.
+    ///
+    /// Returns false if the PublicKey is infinity.
+    pub fn verify_eth2(&self, root: &[u8; 32], domain: &Eth2Domain, pk: &PublicKey) -> bool {
+        if pk.point.is_infinity() {
+            return false;
+        }
+        let msg_hash_point = hash_to_curve_g2(&domain.signing_root(root), ETH2_DST);
+
+        // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
+        let generator_g1_negative = amcl_utils::generator_g1_negative();
+        ate2_evaluation(
+            self.point.as_raw(),
+            &generator_g1_negative,
+            &msg_hash_point,
+            pk.point.as_raw(),
+        )
+    }
+
     /// Verify the Signature against a PublicKey, where the message has already been hashed.
     ///
     /// The supplied hashes will be mapped to G2.
     ///
     /// In theory, should only return true if the PublicKey matches the SecretKey used to
-    /// instantiate the Signature.
+    /// instantiate the Signature. Returns false if the PublicKey is the identity, as `verify`.
     pub fn verify_hashed(
         &self,
         msg_hash_real: &[u8],
         msg_hash_imaginary: &[u8],
         pk: &PublicKey,
     ) -> bool {
+        if pk.point.is_infinity() {
+            return false;
+        }
         let mut msg_hash_point = map_to_g2(msg_hash_real, msg_hash_imaginary);
         msg_hash_point.affine();
         let mut lhs = {
@@ -80,17 +265,314 @@ impl Signature {
         lhs.equals(&mut rhs)
     }
 
+    /// Verify a batch of independent (PublicKey, message, domain, Signature) triples.
+    ///
+    /// The batch is first checked in one go using randomized batch verification (see
+    /// `AggregateSignature::verify_multiple_signatures`). Only if that fails is each triple
+    /// verified individually. Returns the result for each triple, in input order.
+    pub fn verify_batch<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+        triples: &[(&PublicKey, &[u8], u64, &Signature)],
+    ) -> Vec<bool> {
+        if triples.is_empty() {
+            return vec![];
+        }
+
+        let signature_sets = triples.iter().map(|(pk, msg, domain, sig)| {
+            (
+                sig.point.clone(),
+                vec![pk.point.clone()],
+                vec![msg.to_vec()],
+                *domain,
+            )
+        });
+        if AggregateSignature::verify_multiple_signatures(rng, signature_sets) {
+            return vec![true; triples.len()];
+        }
+
+        triples
+            .iter()
+            .map(|(pk, msg, domain, sig)| sig.verify(msg, *domain, pk))
+            .collect()
+    }
+
+    /// Check every one of a batch of independent (PublicKey, message, domain, Signature) triples
+    /// is valid.
+    ///
+    /// As `verify_batch` the batch is checked with one randomized batch verification, but there
+    /// is no fallback to find which triples failed. Returns true if there are no triples and
+    /// false if any PublicKey is infinity.
+    ///
+    /// Each triple is weighted by an independent 63 bit coefficient from `rng`, so a batch with
+    /// an invalid triple is accepted with probability at most 2^-63. This requires the
+    /// Signatures to be in the G2 subgroup, as checked by `from_bytes`, and `rng` to be
+    /// unpredictable to whoever produced the Signatures.
+    pub fn verify_all<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+        triples: &[(&PublicKey, &[u8], u64, &Signature)],
+    ) -> bool {
+        if triples.iter().any(|(pk, _, _, _)| pk.point.is_infinity()) {
+            return false;
+        }
+        if triples.is_empty() {
+            return true;
+        }
+
+        let signature_sets = triples.iter().map(|(pk, msg, domain, sig)| {
+            (
+                sig.point.clone(),
+                vec![pk.point.clone()],
+                vec![msg.to_vec()],
+                *domain,
+            )
+        });
+        AggregateSignature::verify_multiple_signatures(rng, signature_sets)
+    }
+
     /// Instantiate a Signature from compressed bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Signature, DecodeError> {
         let point = G2Point::from_bytes(bytes)?;
         Ok(Self { point })
     }
 
+    /// Instantiate a Signature from compressed bytes, skipping the subgroup check.
+    ///
+    /// Only use this for bytes which are already known to be a valid Signature (see
+    /// `G2Point::from_bytes_unchecked`).
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Signature, DecodeError> {
+        let point = G2Point::from_bytes_unchecked(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Check the Signature is in the G2 subgroup of order r.
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        amcl_utils::is_in_g2_subgroup(self.point.as_raw())
+    }
+
     /// Compress the Signature as bytes.
     pub fn as_bytes(&self) -> Vec<u8> {
         let mut clone = self.point.clone();
         clone.as_bytes()
     }
+
+    /// Encode the compressed bytes as standard padded base64.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.as_bytes())
+    }
+
+    /// Instantiate a Signature from standard padded base64 of the compressed bytes.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(encoded: &str) -> Result<Signature, DecodeError> {
+        let bytes = base64::decode(encoded).map_err(|_| DecodeError::InvalidBase64)?;
+        if bytes.len() != amcl_utils::G2_BYTE_SIZE / 2 {
+            return Err(DecodeError::IncorrectSize);
+        }
+        Self::from_bytes(&bytes)
+    }
+
+    /// Write the compressed bytes to some `io::Write`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_bytes())
+    }
+
+    /// Read exactly 96 compressed bytes from some `io::Read` and decode them.
+    ///
+    /// Decoding failures are returned as `io::ErrorKind::InvalidData`.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read>(reader: &mut R) -> io::Result<Signature> {
+        let mut bytes = [0; amcl_utils::G2_BYTE_SIZE / 2];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        amcl_utils::fmt_hex(f, &self.as_bytes())
+    }
+}
+
+/// Limits applied by a Signer before a message is hashed.
+///
+/// The default is unbounded, matching `Signature::new`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignerConfig {
+    /// The maximum message length in bytes, if any.
+    ///
+    /// A server signing messages from the network should set this, so an absurdly large message
+    /// is rejected before the cost of hashing it.
+    pub max_message_len: Option<usize>,
+}
+
+/// Creates Signatures, optionally rejecting messages which break the limits of a SignerConfig.
+///
+/// A Signer holds no state besides its SignerConfig and produces exactly the same Signatures as
+/// `Signature::new`, it exists so the limits can be configured once and applied by `try_sign`.
+#[derive(Clone, Debug, Default)]
+pub struct Signer {
+    config: SignerConfig,
+}
+
+impl Signer {
+    /// Instantiate a new Signer without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instantiate a new Signer applying the limits of `config`.
+    pub fn with_config(config: SignerConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &SignerConfig {
+        &self.config
+    }
+
+    /// Sign a message with a SecretKey, this is identical to `Signature::new`.
+    ///
+    /// The SignerConfig is not applied, see `try_sign`.
+    pub fn sign(&self, msg: &[u8], d: u64, sk: &SecretKey) -> Signature {
+        Signature::new(msg, d, sk)
+    }
+
+    /// Sign a message with a SecretKey, first checking the limits of the SignerConfig.
+    ///
+    /// Returns `SignError::MessageTooLong` without hashing if the message is longer than
+    /// `max_message_len`, otherwise the Signature is identical to `Signature::new`.
+    pub fn try_sign(&self, msg: &[u8], d: u64, sk: &SecretKey) -> Result<Signature, SignError> {
+        if let Some(max) = self.config.max_message_len {
+            if msg.len() > max {
+                return Err(SignError::MessageTooLong {
+                    len: msg.len(),
+                    max,
+                });
+            }
+        }
+        Ok(self.sign(msg, d, sk))
+    }
+}
+
+/// Verify a compressed Signature against a compressed PublicKey and a message.
+///
+/// Both inputs are decoded with `from_bytes`, including the subgroup checks. A decoding
+/// failure is returned as the `DecodeError`, otherwise the result of `Signature::verify`.
+pub fn verify_bytes(
+    pk_bytes: &[u8],
+    msg: &[u8],
+    domain: u64,
+    sig_bytes: &[u8],
+) -> Result<bool, DecodeError> {
+    let pk = PublicKey::from_bytes(pk_bytes)?;
+    let sig = Signature::from_bytes(sig_bytes)?;
+    Ok(sig.verify(msg, domain, &pk))
+}
+
+/// Cheaply check bytes could be a compressed Signature, without decoding the point.
+///
+/// Only the length (96 bytes) and the compression flags are checked, so malformed input can be
+/// rejected before the expensive decoding and subgroup check of `Signature::from_bytes`, which
+/// is still required. This does not allocate.
+pub fn is_valid_signature_bytes(bytes: &[u8]) -> bool {
+    amcl_utils::has_valid_compressed_flags(bytes, amcl_utils::G2_BYTE_SIZE / 2)
+}
+
+/// Serialize Signatures to a single blob.
+///
+/// The blob is a 4 byte big-endian count followed by the 96 byte compressed bytes of each
+/// Signature.
+pub fn serialize_signatures(sigs: &[&Signature]) -> Vec<u8> {
+    let sig_size = amcl_utils::G2_BYTE_SIZE / 2;
+    let mut bytes = Vec::with_capacity(4 + sigs.len() * sig_size);
+    bytes.extend_from_slice(&(sigs.len() as u32).to_be_bytes());
+    for sig in sigs {
+        bytes.extend_from_slice(&sig.as_bytes());
+    }
+    bytes
+}
+
+/// Deserialize Signatures from a blob created by `serialize_signatures`.
+///
+/// Returns `DecodeError::IncorrectSize` if the blob is not exactly the size given by its count.
+pub fn deserialize_signatures(bytes: &[u8]) -> Result<Vec<Signature>, DecodeError> {
+    let sig_size = amcl_utils::G2_BYTE_SIZE / 2;
+    if bytes.len() < 4 {
+        return Err(DecodeError::IncorrectSize);
+    }
+
+    let mut count = [0; 4];
+    count.copy_from_slice(&bytes[..4]);
+    let count = u32::from_be_bytes(count) as usize;
+
+    let sig_bytes = &bytes[4..];
+    if count.checked_mul(sig_size) != Some(sig_bytes.len()) {
+        return Err(DecodeError::IncorrectSize);
+    }
+
+    sig_bytes
+        .chunks(sig_size)
+        .map(Signature::from_bytes)
+        .collect()
+}
+
+/// Lazily decodes Signatures from a buffer of concatenated 96 byte compressed Signatures.
+///
+/// Each Signature is only decoded and validated when the iterator reaches it.
+pub struct SignatureReader<'a> {
+    chunks: core::slice::Chunks<'a, u8>,
+}
+
+impl<'a> SignatureReader<'a> {
+    /// Returns `DecodeError::IncorrectSize` if the buffer is not a multiple of 96 bytes.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let sig_size = amcl_utils::G2_BYTE_SIZE / 2;
+        if bytes.len() % sig_size != 0 {
+            return Err(DecodeError::IncorrectSize);
+        }
+        Ok(Self {
+            chunks: bytes.chunks(sig_size),
+        })
+    }
+}
+
+impl<'a> Iterator for SignatureReader<'a> {
+    type Item = Result<Signature, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(Signature::from_bytes)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for SignatureReader<'a> {}
+
+/// A Signature from a single participant in a threshold scheme.
+///
+/// The `index` identifies which participant (and so which secret key share) produced the
+/// signature.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartialSignature {
+    pub index: u32,
+    pub sig: Signature,
+}
+
+impl PartialSignature {
+    /// Instantiate a new PartialSignature from a participant index and their Signature.
+    pub fn new(index: u32, sig: Signature) -> Self {
+        Self { index, sig }
+    }
+
+    /// Verify the PartialSignature against the participant's share PublicKey.
+    ///
+    /// Each partial signature should be verified before being combined, so that a single bad
+    /// share cannot corrupt the reconstructed signature.
+    pub fn verify_partial(&self, msg: &[u8], domain: u64, share_pubkey: &PublicKey) -> bool {
+        self.sig.verify(msg, domain, share_pubkey)
+    }
 }
 
 #[cfg(test)]
@@ -159,6 +641,489 @@ mod tests {
         assert_eq!(sig.verify(&msg.as_bytes(), domain, &vk), false);
     }
 
+    #[test]
+    fn test_signature_write_read() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sig = Signature::new(&[42; 32], 42, &keypair.sk);
+        let mut buffer = vec![];
+        sig.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 96);
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(Signature::read_from(&mut reader).unwrap(), sig);
+        assert!(Signature::read_from(&mut reader).is_err());
+
+        // Truncated input
+        let err = Signature::read_from(&mut &buffer[..95]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_signature_debug() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sig = Signature::new(&[42; 32], 42, &keypair.sk);
+        let expected = format!("0x{}", hex::encode(sig.as_bytes()));
+        assert_eq!(format!("{:?}", sig), expected);
+    }
+
+    #[test]
+    fn test_signature_not_in_subgroup() {
+        // ((2, 0), y) is on the curve but not in the subgroup
+        let bytes = hex::decode("a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002").unwrap();
+        assert_eq!(
+            Signature::from_bytes(&bytes),
+            Err(DecodeError::NotInSubgroup)
+        );
+
+        let sig = Signature::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(sig.as_bytes(), bytes);
+        assert!(!sig.is_in_correct_subgroup());
+
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        assert!(Signature::new(&[1; 32], 42, &keypair.sk).is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn test_signature_g2_conversion() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sig = Signature::new(&[42; 32], 1, &keypair.sk);
+        assert_eq!(*sig.as_g2(), sig.point);
+        let point = sig.clone().into_g2();
+        assert_eq!(point, sig.point);
+        assert_eq!(Signature::from_g2(point), sig);
+    }
+
+    #[test]
+    fn test_signature_g2_raw_conversion() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sig = Signature::new(&[42; 32], 1, &keypair.sk);
+        let raw = sig.clone().into_g2_raw();
+        assert_eq!(G2Point::from_raw(raw), sig.point);
+        assert_eq!(Signature::from_g2_raw(raw, true), Ok(sig.clone()));
+        assert_eq!(Signature::from_g2_raw(raw, false), Ok(sig));
+
+        // On the curve but not in the subgroup
+        let raw = amcl_utils::hash_on_g2_no_clear(&[42; 32], 1);
+        assert_eq!(
+            Signature::from_g2_raw(raw, true),
+            Err(DecodeError::NotInSubgroup)
+        );
+        assert!(Signature::from_g2_raw(raw, false).is_ok());
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_signature_base64() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", 42, &keypair.sk);
+        let encoded = signature.to_base64();
+        assert_eq!(encoded.len(), 128);
+        assert_eq!(Signature::from_base64(&encoded), Ok(signature));
+
+        assert_eq!(
+            Signature::from_base64("not base64!"),
+            Err(DecodeError::InvalidBase64)
+        );
+        assert_eq!(
+            Signature::from_base64(&encoded[4..]),
+            Err(DecodeError::IncorrectSize)
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+        let domain = 42;
+
+        let identity = Signature::identity();
+        assert!(identity.point.is_infinity());
+        assert_eq!(identity.point, AggregateSignature::new().point);
+        assert_eq!(
+            Signature::from_bytes(&identity.as_bytes()).unwrap(),
+            identity
+        );
+        assert!(!identity.verify(msg, domain, &keypair.pk));
+
+        let signature = Signature::new(msg, domain, &keypair.sk);
+        let identity_pk = PublicKey::identity();
+        assert!(identity_pk.point.is_infinity());
+        assert!(!signature.verify(msg, domain, &identity_pk));
+        assert!(!identity.verify(msg, domain, &identity_pk));
+        assert!(!identity.verify_ref(msg, domain, &identity_pk));
+        assert_eq!(
+            identity_pk.key_validate(),
+            Err(DecodeError::PointAtInfinityDisallowed)
+        );
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = [1; 32];
+        let sig = Signature::new(&msg, 42, &keypair.sk);
+
+        let blinding = SecretKey::random(&mut rand::thread_rng()).x;
+        let (new_sig, new_pk) = sig.rerandomize(&keypair.pk, &blinding);
+        assert!(new_sig.verify(&msg, 42, &new_pk));
+        assert_ne!(new_sig, sig);
+        assert_ne!(new_pk, keypair.pk);
+
+        // The rerandomized Signature only verifies with the rerandomized PublicKey
+        assert!(!new_sig.verify(&msg, 42, &keypair.pk));
+        assert!(!sig.verify(&msg, 42, &new_pk));
+        assert!(!new_sig.verify(&[2; 32], 42, &new_pk));
+    }
+
+    #[test]
+    fn test_eth2_known_answer() {
+        // Key, PublicKey and Signature of the consensus-spec bls/sign case for the zero message,
+        // also in interop_sign.yml
+        let sk = SecretKey::from_be_bytes_32(
+            &hex::decode("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3")
+                .unwrap(),
+        )
+        .unwrap();
+        let pk = PublicKey::from_secret_key(&sk);
+        assert_eq!(pk.as_bytes(), hex::decode("a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a").unwrap());
+        let sig = sk.sign_prehashed(&G2Point::from_raw(hash_to_curve_g2(&[0; 32], ETH2_DST)));
+        assert_eq!(sig.as_bytes(), hex::decode("b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55").unwrap());
+
+        // new_eth2 signs the signing root in the same way, see test_signing_root in eth2.rs for
+        // the signing root itself
+        let domain = Eth2Domain::compute([3, 0, 0, 0], [0; 4], &[0; 32]);
+        let mut root = [0 as u8; 32];
+        for (i, byte) in root.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let signing_root = domain.signing_root(&root);
+        let sig = Signature::new_eth2(&root, &domain, &sk);
+        assert_eq!(
+            sig,
+            sk.sign_prehashed(&G2Point::from_raw(hash_to_curve_g2(
+                &signing_root,
+                ETH2_DST
+            )))
+        );
+        assert!(sig.verify_eth2(&root, &domain, &pk));
+    }
+
+    #[test]
+    fn test_verify_eth2() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let domain = Eth2Domain::compute([7, 0, 0, 0], [1, 0, 0, 0], &[9; 32]);
+        let root = [7; 32];
+        let sig = Signature::new_eth2(&root, &domain, &keypair.sk);
+        assert!(sig.verify_eth2(&root, &domain, &keypair.pk));
+
+        // Wrong root, domain or key
+        let other = Keypair::random(&mut rand::thread_rng());
+        assert!(!sig.verify_eth2(&[8; 32], &domain, &keypair.pk));
+        assert!(!sig.verify_eth2(&root, &Eth2Domain([0; 32]), &keypair.pk));
+        assert!(!sig.verify_eth2(&root, &domain, &other.pk));
+
+        // Infinity
+        let infinity = PublicKey::new_from_raw(&amcl_utils::GroupG1::new());
+        assert!(!Signature::identity().verify_eth2(&root, &domain, &infinity));
+    }
+
+    #[test]
+    fn test_verify_signing_root() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let root = [7; 32];
+        let sig = Signature::new(&root, 42, &keypair.sk);
+        assert!(sig.verify_signing_root(&root, 42, &keypair.pk));
+        assert!(!sig.verify_signing_root(&[8; 32], 42, &keypair.pk));
+        assert!(!sig.verify_signing_root(&root, 43, &keypair.pk));
+    }
+
+    #[test]
+    fn test_signer_config() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signer = Signer::new();
+        assert_eq!(signer.config().max_message_len, None);
+        let msg = vec![7; 133_700];
+        assert_eq!(
+            signer.try_sign(&msg, 42, &keypair.sk),
+            Ok(Signature::new(&msg, 42, &keypair.sk))
+        );
+
+        let signer = Signer::with_config(SignerConfig {
+            max_message_len: Some(32),
+        });
+        assert_eq!(
+            signer.try_sign(&msg, 42, &keypair.sk),
+            Err(SignError::MessageTooLong {
+                len: 133_700,
+                max: 32
+            })
+        );
+        assert_eq!(
+            signer.try_sign(&msg[..33], 42, &keypair.sk),
+            Err(SignError::MessageTooLong { len: 33, max: 32 })
+        );
+        for len in &[0, 1, 32] {
+            assert_eq!(
+                signer.try_sign(&msg[..*len], 42, &keypair.sk),
+                Ok(Signature::new(&msg[..*len], 42, &keypair.sk))
+            );
+        }
+    }
+
+    #[test]
+    fn test_domain_bytes() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = [1 as u8; 32];
+        let domain = b"some application domain";
+        let sig = Signature::sign_with_domain_bytes(&msg, domain, &keypair.sk);
+        assert!(sig.verify_with_domain_bytes(&msg, domain, &keypair.pk));
+        assert!(!sig.verify_with_domain_bytes(&msg, b"another application domain", &keypair.pk));
+        assert!(!sig.verify_with_domain_bytes(&[2; 32], domain, &keypair.pk));
+
+        // A u64 domain is its big-endian bytes
+        let sig = Signature::sign_with_domain_bytes(&msg, &(42 as u64).to_be_bytes(), &keypair.sk);
+        assert_eq!(sig, Signature::new(&msg, 42, &keypair.sk));
+        assert!(sig.verify(&msg, 42, &keypair.pk));
+        assert!(
+            Signature::new(&msg, 42, &keypair.sk).verify_with_domain_bytes(
+                &msg,
+                &(42 as u64).to_be_bytes(),
+                &keypair.pk
+            )
+        );
+    }
+
+    #[test]
+    fn test_verify_bytes() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk_bytes = keypair.pk.as_bytes();
+        let sig_bytes = Signature::new(&[42; 32], 1, &keypair.sk).as_bytes();
+        assert_eq!(verify_bytes(&pk_bytes, &[42; 32], 1, &sig_bytes), Ok(true));
+
+        // Cryptographic failures
+        assert_eq!(verify_bytes(&pk_bytes, &[43; 32], 1, &sig_bytes), Ok(false));
+        assert_eq!(verify_bytes(&pk_bytes, &[42; 32], 2, &sig_bytes), Ok(false));
+        let other = Keypair::random(&mut rand::thread_rng()).pk.as_bytes();
+        assert_eq!(verify_bytes(&other, &[42; 32], 1, &sig_bytes), Ok(false));
+
+        // Decoding failures
+        assert_eq!(
+            verify_bytes(&pk_bytes[1..], &[42; 32], 1, &sig_bytes),
+            Err(DecodeError::IncorrectSize)
+        );
+        assert_eq!(
+            verify_bytes(&pk_bytes, &[42; 32], 1, &sig_bytes[1..]),
+            Err(DecodeError::IncorrectSize)
+        );
+        let mut bad_sig = sig_bytes.clone();
+        bad_sig[0] &= 0x7f;
+        assert_eq!(
+            verify_bytes(&pk_bytes, &[42; 32], 1, &bad_sig),
+            Err(DecodeError::InvalidCFlag)
+        );
+    }
+
+    #[test]
+    fn test_is_valid_signature_bytes() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let mut bytes = Signature::new(&[42; 32], 1, &keypair.sk).as_bytes();
+        assert!(is_valid_signature_bytes(&bytes));
+        assert!(is_valid_signature_bytes(&Signature::identity().as_bytes()));
+
+        // Wrong lengths, e.g. a PublicKey
+        assert!(!is_valid_signature_bytes(&[]));
+        assert!(!is_valid_signature_bytes(&bytes[..48]));
+        assert!(!is_valid_signature_bytes(&[&bytes[..], &[0]].concat()));
+
+        // Infinity with other bits set
+        let mut infinity = Signature::identity().as_bytes();
+        infinity[95] = 1;
+        assert!(!is_valid_signature_bytes(&infinity));
+
+        // c_flag unset
+        bytes[0] &= 0x7f;
+        assert!(!is_valid_signature_bytes(&bytes));
+    }
+
+    #[test]
+    fn test_serialize_signatures() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sigs: Vec<Signature> = (0..3)
+            .map(|i| Signature::new(&[i; 32], 42, &keypair.sk))
+            .collect();
+        let sig_refs: Vec<&Signature> = sigs.iter().collect();
+
+        let bytes = serialize_signatures(&sig_refs);
+        assert_eq!(bytes.len(), 4 + 3 * 96);
+        assert_eq!(&bytes[..4], &[0, 0, 0, 3]);
+        assert_eq!(deserialize_signatures(&bytes).unwrap(), sigs);
+
+        // Empty
+        let bytes = serialize_signatures(&[]);
+        assert_eq!(bytes, vec![0, 0, 0, 0]);
+        assert!(deserialize_signatures(&bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_signature_reader() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signatures: Vec<Signature> = (0..3)
+            .map(|i| Signature::new(&[i; 32], 42, &keypair.sk))
+            .collect();
+        let mut bytes = vec![];
+        for signature in &signatures {
+            bytes.extend_from_slice(&signature.as_bytes());
+        }
+
+        let reader = SignatureReader::new(&bytes).unwrap();
+        assert_eq!(reader.len(), 3);
+        let decoded: Result<Vec<Signature>, DecodeError> = reader.collect();
+        assert_eq!(decoded.unwrap(), signatures);
+        assert_eq!(SignatureReader::new(&[]).unwrap().count(), 0);
+
+        // Not a multiple of 96 bytes
+        assert_eq!(
+            SignatureReader::new(&bytes[1..]).err(),
+            Some(DecodeError::IncorrectSize)
+        );
+
+        // Only the bad chunk fails to decode
+        bytes[96] = 0;
+        let mut reader = SignatureReader::new(&bytes).unwrap();
+        assert_eq!(reader.next().unwrap(), Ok(signatures[0].clone()));
+        assert_eq!(reader.next().unwrap(), Err(DecodeError::InvalidCFlag));
+        assert_eq!(reader.next().unwrap(), Ok(signatures[2].clone()));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_signatures_incorrect_size() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let sig = Signature::new(&[1; 32], 42, &keypair.sk);
+        let bytes = serialize_signatures(&[&sig, &sig]);
+
+        // Truncated
+        assert_eq!(
+            deserialize_signatures(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::IncorrectSize)
+        );
+        assert_eq!(
+            deserialize_signatures(&bytes[..3]),
+            Err(DecodeError::IncorrectSize)
+        );
+
+        // Count too high and too low
+        let mut miscounted = bytes.clone();
+        miscounted[3] = 3;
+        assert_eq!(
+            deserialize_signatures(&miscounted),
+            Err(DecodeError::IncorrectSize)
+        );
+        miscounted[3] = 1;
+        assert_eq!(
+            deserialize_signatures(&miscounted),
+            Err(DecodeError::IncorrectSize)
+        );
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let domain = 42;
+        let keypairs: Vec<Keypair> = (0..5)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let msgs: Vec<Vec<u8>> = (0..5).map(|i| vec![i as u8; 32]).collect();
+        let mut sigs: Vec<Signature> = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .map(|(kp, msg)| Signature::new(msg, domain, &kp.sk))
+            .collect();
+
+        let triples: Vec<(&PublicKey, &[u8], u64, &Signature)> = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .zip(sigs.iter())
+            .map(|((kp, msg), sig)| (&kp.pk, msg.as_slice(), domain, sig))
+            .collect();
+        assert_eq!(
+            Signature::verify_batch(&mut rand::thread_rng(), &triples),
+            vec![true; 5]
+        );
+
+        // Shift part of one signature onto another, both should be reported invalid
+        let mut offset = Signature::new(&[9; 32], domain, &keypairs[0].sk).point;
+        sigs[1].point.add(&offset);
+        offset = G2Point::from_raw({
+            let mut raw = offset.into_raw();
+            raw.neg();
+            raw
+        });
+        sigs[3].point.add(&offset);
+
+        let triples: Vec<(&PublicKey, &[u8], u64, &Signature)> = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .zip(sigs.iter())
+            .map(|((kp, msg), sig)| (&kp.pk, msg.as_slice(), domain, sig))
+            .collect();
+        assert_eq!(
+            Signature::verify_batch(&mut rand::thread_rng(), &triples),
+            vec![true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_verify_all() {
+        let domain = 42;
+        let keypairs: Vec<Keypair> = (0..5)
+            .map(|_| Keypair::random(&mut rand::thread_rng()))
+            .collect();
+        let msgs: Vec<Vec<u8>> = (0..5).map(|i| vec![i as u8; 32]).collect();
+        let sigs: Vec<Signature> = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .map(|(kp, msg)| Signature::new(msg, domain, &kp.sk))
+            .collect();
+
+        let mut triples: Vec<(&PublicKey, &[u8], u64, &Signature)> = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .zip(sigs.iter())
+            .map(|((kp, msg), sig)| (&kp.pk, msg.as_slice(), domain, sig))
+            .collect();
+        assert!(Signature::verify_all(&mut rand::thread_rng(), &triples));
+        assert!(Signature::verify_all(&mut rand::thread_rng(), &[]));
+
+        // One Signature swapped onto another Message
+        triples[2].3 = &sigs[3];
+        assert!(!Signature::verify_all(&mut rand::thread_rng(), &triples));
+
+        // Infinity PublicKey and Signature
+        let infinity_pk = PublicKey::identity();
+        let infinity_sig = Signature {
+            point: G2Point::new(),
+        };
+        let infinity_triple: (&PublicKey, &[u8], u64, &Signature) =
+            (&infinity_pk, msgs[0].as_slice(), domain, &infinity_sig);
+        assert!(!Signature::verify_all(
+            &mut rand::thread_rng(),
+            &[infinity_triple]
+        ));
+    }
+
+    #[test]
+    fn test_verify_partial() {
+        let share = Keypair::random(&mut rand::thread_rng());
+        let other_share = Keypair::random(&mut rand::thread_rng());
+        let msg = "Some msg".as_bytes();
+        let domain = 42;
+
+        let partial = PartialSignature::new(3, Signature::new(&msg, domain, &share.sk));
+        assert!(partial.verify_partial(&msg, domain, &share.pk));
+        assert!(!partial.verify_partial(&msg, domain, &other_share.pk));
+        assert!(!partial.verify_partial(&msg, domain + 1, &share.pk));
+    }
+
     #[test]
     // Test vectors use Keccak whilst this implementation uses SHA2.
     #[should_panic]
@@ -214,4 +1179,58 @@ mod tests {
             assert_eq!(output, compressed_sig);
         }
     }
+
+    #[test]
+    fn test_interop_sign_vectors() {
+        // Run tests from interop_sign.yml, which are shared with other implementations
+        let mut file = {
+            let mut file_path_buf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            file_path_buf.push("src/test_vectors/interop_sign.yml");
+
+            File::open(file_path_buf).unwrap()
+        };
+        let mut yaml_str = String::new();
+        file.read_to_string(&mut yaml_str).unwrap();
+        let docs = yaml::YamlLoader::load_from_str(&yaml_str).unwrap();
+        let doc = &docs[0];
+
+        let decode = |value: &yaml::Yaml| {
+            hex::decode(value.as_str().unwrap().trim_start_matches("0x")).unwrap()
+        };
+        let negative_g1 = amcl_utils::generator_g1_negative();
+
+        let test_cases = doc["test_cases"].as_vec().unwrap();
+        assert_eq!(test_cases.len(), 9);
+        for test_case in test_cases {
+            let sk = SecretKey::from_be_bytes_32(&decode(&test_case["input"]["privkey"])).unwrap();
+            let msg = decode(&test_case["input"]["message"]);
+            let pk_bytes = decode(&test_case["pubkey"]);
+            let output = decode(&test_case["output"]);
+
+            let pk = PublicKey::from_secret_key(&sk);
+            assert_eq!(pk.as_bytes(), pk_bytes);
+            assert_eq!(PublicKey::from_bytes(&pk_bytes).unwrap(), pk);
+
+            // Signing reproduces the same bytes
+            let hash_point = G2Point::from_raw(hash_to_curve_g2(&msg, ETH2_DST));
+            let sig = sk.sign_prehashed(&hash_point);
+            assert_eq!(sig.as_bytes(), output);
+
+            // The given Signature verifies, but not for another message
+            let sig = Signature::from_bytes(&output).unwrap();
+            assert!(ate2_evaluation(
+                sig.point.as_raw(),
+                &negative_g1,
+                hash_point.as_raw(),
+                pk.point.as_raw(),
+            ));
+            let other_point = hash_to_curve_g2(&[&msg[..], &[0]].concat(), ETH2_DST);
+            assert!(!ate2_evaluation(
+                sig.point.as_raw(),
+                &negative_g1,
+                &other_point,
+                pk.point.as_raw(),
+            ));
+        }
+    }
 }
@@ -0,0 +1,137 @@
+//! Deterministic test vector generation.
+//!
+//! Every value is derived from a seed with SHA256, so the vectors only change if the encoding,
+//! hashing or signing changes. Snapshot the output of `generate` to detect such changes.
+
+extern crate hex;
+extern crate serde;
+
+use self::serde::Serialize;
+use super::aggregates::{AggregatePublicKey, AggregateSignature};
+use super::amcl_utils::{hash, MOD_BYTE_SIZE};
+use super::keys::{Keypair, PublicKey, SecretKey};
+use super::signature::Signature;
+
+/// A SecretKey and its PublicKey, as `0x` prefixed hex.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KeypairVector {
+    pub secret_key: String,
+    pub public_key: String,
+}
+
+/// A set of deterministic vectors, with all bytes as `0x` prefixed hex.
+///
+/// Keypair `i` signs message `i` to give signature `i`. The aggregates are over all of them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TestVectors {
+    pub seed: u64,
+    pub domain: u64,
+    pub keypairs: Vec<KeypairVector>,
+    pub messages: Vec<String>,
+    pub signatures: Vec<String>,
+    pub aggregate_public_key: String,
+    pub aggregate_signature: String,
+}
+
+/// Generate `count` deterministic Keypairs, Messages and Signatures from `seed`.
+///
+/// SecretKey `i` is SHA256(seed || i || "secret key") reduced modulo the curve order and
+/// Message `i` is SHA256(seed || i || "message"), with seed and i as 8 big-endian bytes. The
+/// seed is also used as the domain.
+pub fn generate(seed: u64, count: u64) -> TestVectors {
+    let domain = seed;
+    let mut keypairs = vec![];
+    let mut messages = vec![];
+    let mut signatures = vec![];
+    let mut aggregate_public_key = AggregatePublicKey::new();
+    let mut aggregate_signature = AggregateSignature::new();
+
+    for i in 0..count {
+        let keypair = keypair(seed, i);
+        let msg = message(seed, i);
+        let signature = Signature::new(&msg, domain, &keypair.sk);
+        aggregate_public_key.add(&keypair.pk);
+        aggregate_signature.add(&signature);
+
+        keypairs.push(KeypairVector {
+            secret_key: to_hex(&keypair.sk.as_bytes()),
+            public_key: to_hex(&keypair.pk.as_bytes()),
+        });
+        messages.push(to_hex(&msg));
+        signatures.push(to_hex(&signature.as_bytes()));
+    }
+
+    TestVectors {
+        seed,
+        domain,
+        keypairs,
+        messages,
+        signatures,
+        aggregate_public_key: to_hex(&aggregate_public_key.as_bytes()),
+        aggregate_signature: to_hex(&aggregate_signature.as_bytes()),
+    }
+}
+
+/// The deterministic Keypair at `index` for `seed`, see `generate`.
+pub fn keypair(seed: u64, index: u64) -> Keypair {
+    let digest = hash(&[&seed.to_be_bytes()[..], &index.to_be_bytes(), b"secret key"].concat());
+    let mut bytes = vec![0; MOD_BYTE_SIZE];
+    let offset = bytes.len() - digest.len();
+    bytes[offset..].copy_from_slice(&digest);
+    let sk = SecretKey::from_bytes(&bytes).unwrap();
+    let sk = SecretKey { x: sk.as_scalar() };
+    let pk = PublicKey::from_secret_key(&sk);
+    Keypair { sk, pk }
+}
+
+/// The deterministic Message at `index` for `seed`, see `generate`.
+pub fn message(seed: u64, index: u64) -> Vec<u8> {
+    hash(&[&seed.to_be_bytes()[..], &index.to_be_bytes(), b"message"].concat())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_snapshot() {
+        let vectors = generate(1, 2);
+        assert_eq!(vectors, generate(1, 2));
+        assert_ne!(vectors, generate(2, 2));
+
+        assert_eq!(
+            vectors.keypairs[0].secret_key,
+            "0x0000000000000000000000000000000009300f548bcabc47baf774f20d348cc863538ccaa227803fbda55f164637f4c8"
+        );
+        assert_eq!(
+            vectors.keypairs[0].public_key,
+            "0xaa97329a7c63ac8f06c3fa242bb808984228f7b41e0d300f0e67d0888fa7c22aef8a26999593ccd5819c0ef29f9da5d4"
+        );
+        assert_eq!(
+            vectors.messages[0],
+            "0xdc22e511a5cc2f876209d36b8a2ad94ed8a98748781394e9059a7aadb11ee4a3"
+        );
+        assert_eq!(
+            vectors.signatures[0],
+            "0x8cf51bd9d3d14185e0959e4267357daee4c6c4e93e5be38b506cc1167e4b0e91b105d7b63adeb4c75a7ee1cf4f642f8102896b818a8a5b9504d089d3408fb5d1162d698d6d91d07d25232806953106f064fa199622dacc8610fafb3c497f0f4c"
+        );
+        assert_eq!(
+            vectors.aggregate_public_key,
+            "0xaac25ae6c620c89d7477d2f1b1955f5292e20d7c8f849a6740c6eeb86514562d8b8f53f80555993c35d3c83473df21ab"
+        );
+        assert_eq!(
+            vectors.aggregate_signature,
+            "0x949c754fcb47cb2f659aaa07fa06ac6824fbdbab38cb308fb869585173c3ba2d29bc28cf6ef03050a12f67dfeb57f589134933606e579132cc98f43c64e4f5a6931ad149754564d9f78977b03534fb901024beb8544ae36f9ef49c81140f28ec"
+        );
+
+        let json = serde_json::to_value(&vectors).unwrap();
+        assert_eq!(json["seed"], 1);
+        assert_eq!(json["signatures"][0], vectors.signatures[0].as_str());
+    }
+}